@@ -1,19 +1,3 @@
-use leology::deployment_cost;
-use leology::generate_bindings;
-use leology::Account;
-use leology::Deployment;
-use leology::Nw;
-use leology::Package;
-use leology::Transaction;
-use leology::DEFAULT_ENDPOINT;
-use leology::VM;
+use leology::*;
 
-generate_bindings! {
-    Dev, {
-        [
-        { create_record, (to: Address<Nw>, number: u64), (Rec, Future<Nw>) },
-        { consume_record, (record: Rec), (Future<Nw>) },
-        ],
-        [{ Rec, (number: u64) }],
-    }
-}
+include!(concat!(env!("OUT_DIR"), "/bindings.rs"));
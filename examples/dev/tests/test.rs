@@ -1,23 +1,257 @@
 #[cfg(test)]
 mod tests {
-    use leology::*;
-
     include!("dev.rs");
 
+    /// The seed of the test accounts.
+    const SEED: u64 = 42;
+
+    /// Returns the test accounts, and a local ledger funding Alice in its genesis block.
+    fn setup() -> (TestAccounts<Nw>, LocalLedger<Nw>) {
+        let accounts = TestAccounts::derive(SEED, 3).unwrap();
+        let ledger = LocalLedger::new(accounts.alice().unwrap().private_key()).unwrap();
+        (accounts, ledger)
+    }
+
     #[test]
     fn devtest() {
         //let credits: Program<Nw> = Program::credits().unwrap();
         // Privately mint 100 tokens for Bob.
         //let alice = new_account(Some("0".to_string())).unwrap();
-        let alice =
-            Account::try_from("APrivateKey1zkp8CZNn3yeCseEtxuVPbDCwSyhGW6yZKUYKfgXmcpoGPWH")
-                .unwrap();
-        let dev = Dev::new(&alice).unwrap();
-        let (record, future) = dev.create_record(&alice, alice.address(), 10u64).unwrap();
+        let alice = Account::<Nw>::try_from(leology::devnet::DEV_PRIVATE_KEY).unwrap();
+        let devnet = Devnet::from_env().unwrap();
+        leology::devnet::wait_until_ready(devnet.config()).unwrap();
+        let dev = Dev::deploy_or_attach(&alice, devnet).unwrap();
+        let (record, _future) = dev.create_record(&alice, alice.address(), 10u64).unwrap();
         println!("{:?}", record.number());
         println!("{:#?}", record);
-        assert_eq!(record.number(), 10u64);
+        assert_eq!(record.number().unwrap(), 10u64);
         let future = dev.consume_record(&alice, record).unwrap();
         dbg!(future);
     }
+
+    #[test]
+    fn devtest_local_ledger() {
+        let (accounts, ledger) = setup();
+        let alice = accounts.alice().unwrap();
+        let dev = Dev::deploy(alice, ledger).unwrap();
+        let (record, _future) = dev.create_record(alice, alice.address(), 10u64).unwrap();
+        assert_eq!(record.number().unwrap(), 10u64);
+        assert_eq!(dev.mapping_number(&alice.address()).unwrap(), Some(10u64));
+        dev.consume_record(alice, record).unwrap();
+        assert_eq!(dev.mapping_number(&alice.address()).unwrap(), Some(100u64));
+    }
+
+    #[test]
+    fn devtest_snapshot_revert() {
+        let (accounts, ledger) = setup();
+        let alice = accounts.alice().unwrap();
+        let dev = Dev::deploy(alice, ledger.clone()).unwrap();
+        let snapshot = ledger.snapshot();
+        let height = ledger.latest_height();
+
+        dev.create_record(alice, alice.address(), 10u64).unwrap();
+        assert_eq!(dev.mapping_number(&alice.address()).unwrap(), Some(10u64));
+        ledger.revert(snapshot).unwrap();
+        assert_eq!(ledger.latest_height(), height);
+        assert_eq!(dev.mapping_number(&alice.address()).unwrap(), None);
+
+        // The ledger keeps producing blocks after the revert.
+        dev.create_record(alice, alice.address(), 20u64).unwrap();
+        assert_eq!(dev.mapping_number(&alice.address()).unwrap(), Some(20u64));
+    }
+
+    #[test]
+    fn devtest_advance_blocks() {
+        let (_, ledger) = setup();
+        let height = ledger.latest_height();
+        assert_eq!(ledger.advance_blocks(3).unwrap().height(), height + 3);
+
+        let timestamp = ledger.latest_block().timestamp() + 3600;
+        assert!(ledger.set_next_block_timestamp(timestamp - 7200).is_err());
+        ledger.set_next_block_timestamp(timestamp).unwrap();
+        assert_eq!(ledger.advance_blocks(1).unwrap().timestamp(), timestamp);
+        assert!(ledger.advance_blocks(1).unwrap().timestamp() > timestamp);
+    }
+
+    #[test]
+    fn devtest_wallet() {
+        let (accounts, ledger) = setup();
+        let alice = accounts.alice().unwrap();
+        let wallet = alice.wallet(ledger.clone());
+        let dev = Dev::deploy(alice, ledger.clone()).unwrap();
+        // The genesis block funds Alice with credits records.
+        let credits = wallet.unspent_credits().unwrap();
+        assert!(credits > 0);
+
+        dev.create_record(alice, alice.address(), 10u64).unwrap();
+        dev.create_record(alice, alice.address(), 20u64).unwrap();
+        let numbers: Vec<u64> = wallet
+            .records::<Rec>()
+            .unwrap()
+            .iter()
+            .map(|record| record.number().unwrap())
+            .collect();
+        assert_eq!(numbers, [10, 20]);
+
+        let record = wallet.records::<Rec>().unwrap().remove(0);
+        assert!(!record.is_spent(alice, &ledger).unwrap());
+        dev.consume_record(alice, record.clone()).unwrap();
+        assert!(record.is_spent(alice, &ledger).unwrap());
+        let records = wallet.records::<Rec>().unwrap();
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].number().unwrap(), 20);
+        assert_eq!(wallet.unspent_credits().unwrap(), credits);
+    }
+
+    #[test]
+    fn devtest_credits() {
+        let (accounts, ledger) = setup();
+        let alice = accounts.alice().unwrap();
+        let bob = accounts.bob().unwrap();
+        let credits = Credits::new(ledger.clone()).unwrap();
+
+        credits
+            .transfer_public(alice, bob.address(), 1_000_000)
+            .unwrap();
+        assert_eq!(credits.account(&bob.address()).unwrap(), Some(1_000_000));
+
+        let (record, _future) = credits
+            .transfer_public_to_private(bob, bob.address(), 400_000)
+            .unwrap();
+        let (first, second) = credits.split(bob, record, 100_000).unwrap();
+        assert_eq!(first.microcredits().unwrap(), 100_000);
+        // `split` deducts its fee of 10_000 microcredits from the second record.
+        assert_eq!(second.microcredits().unwrap(), 290_000);
+        let joined = credits.join(bob, first, second).unwrap();
+        let (change, _future) = credits
+            .transfer_private_to_public(bob, joined, alice.address(), 50_000)
+            .unwrap();
+        assert_eq!(change.microcredits().unwrap(), 340_000);
+        assert_eq!(
+            bob.wallet(ledger.clone())
+                .records::<CreditsRecord>()
+                .unwrap()
+                .len(),
+            1
+        );
+        assert_eq!(credits.bonded(&bob.address()).unwrap(), None);
+
+        // Alice sends a private record to Bob, which only Bob can decrypt on chain.
+        let record = alice
+            .wallet(ledger.clone())
+            .records::<CreditsRecord>()
+            .unwrap()
+            .remove(0);
+        let microcredits = record.microcredits().unwrap();
+        let (sent, change) = credits
+            .transfer_private(alice, record, bob.address(), 5_000)
+            .unwrap();
+        assert_eq!(**sent.record.owner(), bob.address());
+        assert_eq!(sent.microcredits().unwrap(), 5_000);
+        assert_eq!(**change.record.owner(), alice.address());
+        assert_eq!(change.microcredits().unwrap(), microcredits - 5_000);
+    }
+
+    #[test]
+    fn devtest_record_to_another_account() {
+        let (accounts, ledger) = setup();
+        let alice = accounts.alice().unwrap();
+        let bob = accounts.bob().unwrap();
+        let dev = Dev::deploy(alice, ledger.clone()).unwrap();
+        // Alice cannot decrypt the record from the transaction, as Bob owns it.
+        let (record, _future) = dev.create_record(alice, bob.address(), 10u64).unwrap();
+        assert_eq!(**record.record.owner(), bob.address());
+        assert_eq!(record.number().unwrap(), 10u64);
+        assert_eq!(bob.wallet(ledger).records::<Rec>().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn devtest_private_fee() {
+        let (accounts, ledger) = setup();
+        let alice = accounts.alice().unwrap();
+        let dev =
+            Dev::from_directory(alice, "build", ledger.clone(), FeeStrategy::AutoRecord).unwrap();
+        let public_balance = ledger.get_public_balance(&alice.address()).unwrap();
+        dev.create_record(alice, alice.address(), 10u64).unwrap();
+        assert_eq!(
+            ledger.get_public_balance(&alice.address()).unwrap(),
+            public_balance
+        );
+
+        // Once the given record is spent, the next fees are paid from other records.
+        let record = alice
+            .wallet(ledger.clone())
+            .records::<CreditsRecord>()
+            .unwrap()[0]
+            .clone();
+        let dev = dev.with_fee_strategy(FeeStrategy::Record(Box::new(record.record().clone())));
+        dev.create_record(alice, alice.address(), 10u64).unwrap();
+        assert!(record.is_spent(alice, &ledger).unwrap());
+        dev.create_record(alice, alice.address(), 10u64).unwrap();
+        assert_eq!(
+            ledger.get_public_balance(&alice.address()).unwrap(),
+            public_balance
+        );
+    }
+
+    #[test]
+    fn devtest_fee_estimate() {
+        let (accounts, ledger) = setup();
+        let alice = accounts.alice().unwrap();
+        let dev = Dev::deploy(alice, ledger.clone())
+            .unwrap()
+            .with_priority_fee(5);
+        let args: [Value<Nw>; 2] = [alice.address().to_value(), 10u64.to_value()];
+        let estimate = dev.estimate_fee(alice, "create_record", &args).unwrap();
+        assert_eq!(estimate.priority_fee, 5);
+        assert!(estimate.finalize_cost > 0);
+
+        let public_balance = ledger.get_public_balance(&alice.address()).unwrap();
+        dev.create_record(alice, alice.address(), 10u64).unwrap();
+        assert_eq!(
+            ledger.get_public_balance(&alice.address()).unwrap(),
+            public_balance - estimate.total()
+        );
+    }
+
+    #[test]
+    fn devtest_attach() {
+        let (accounts, ledger) = setup();
+        let alice = accounts.alice().unwrap();
+        assert!(Dev::attach("dev.aleo", ledger.clone()).is_err());
+
+        let deployed = Dev::deploy_or_attach(alice, ledger.clone()).unwrap();
+        let height = ledger.latest_height();
+        let attached = Dev::deploy_or_attach(alice, ledger.clone()).unwrap();
+        assert_eq!(ledger.latest_height(), height);
+        assert_eq!(attached.program, deployed.program);
+        assert_eq!(
+            Dev::attach("dev.aleo", ledger).unwrap().program_id(),
+            deployed.program_id()
+        );
+    }
+
+    #[test]
+    fn devtest_test_accounts() {
+        let genesis = Account::<Nw>::try_from(leology::devnet::DEV_PRIVATE_KEY).unwrap();
+        let ledger = LocalLedger::new(genesis.private_key()).unwrap();
+        let accounts = TestAccounts::setup(&ledger, SEED, 3, 1_000_000).unwrap();
+        for (_, account) in accounts.iter() {
+            assert_eq!(
+                ledger.get_public_balance(&account.address()).unwrap(),
+                1_000_000
+            );
+        }
+
+        // Accounts holding enough credits are not funded again.
+        let height = ledger.latest_height();
+        accounts.fund(&genesis, &ledger, 1_000_000).unwrap();
+        assert_eq!(ledger.latest_height(), height);
+        for (_, account) in accounts.iter() {
+            assert_eq!(
+                ledger.get_public_balance(&account.address()).unwrap(),
+                1_000_000
+            );
+        }
+    }
 }
@@ -0,0 +1,4 @@
+fn main() {
+    leology::bindings::codegen::build_bindings(".", "bindings.rs")
+        .expect("Failed to generate the program bindings");
+}
@@ -0,0 +1,81 @@
+use crate::bindings::*;
+use crate::*;
+
+/// The chain the generated program bindings deploy to and execute against.
+///
+/// Implemented by [`Devnet`](crate::Devnet) for a snarkOS node reachable over HTTP, and by
+/// [`LocalLedger`](crate::LocalLedger) for an in-memory ledger.
+pub trait Backend<N: Network>: Send + Sync {
+    /// Returns the deployed program with the given ID, if any.
+    fn find_program(&self, program_id: &ProgramID<N>) -> Result<Option<Program<N>>>;
+
+    /// Returns the value stored under `key` in the program's mapping, if any.
+    fn get_mapping_value(
+        &self,
+        program_id: &ProgramID<N>,
+        mapping_name: &Identifier<N>,
+        key: &Plaintext<N>,
+    ) -> Result<Option<Value<N>>>;
+
+    /// Returns the query used to fetch state paths while building transactions.
+    fn query(&self) -> Query<N, BlockMemory<N>>;
+
+    /// Broadcasts the transaction and waits for it to be included in a block.
+    fn broadcast_transaction(&self, transaction: Transaction<N>) -> Result<Confirmation<N>>;
+
+    /// Returns the height of the latest block.
+    fn get_latest_height(&self) -> Result<u32>;
+
+    /// Returns the block at the given height.
+    fn get_block(&self, height: u32) -> Result<Block<N>>;
+
+    /// Returns `true` if a transaction spending the record with the given serial number was accepted.
+    fn contains_serial_number(&self, serial_number: &Field<N>) -> Result<bool>;
+
+    /// Returns the account owning the public balance created in the genesis block.
+    fn genesis_account(&self) -> Result<Account<N>>;
+
+    /// Returns the deployed program with the given ID.
+    fn get_program(&self, program_id: &ProgramID<N>) -> Result<Program<N>> {
+        self.find_program(program_id)?
+            .ok_or_else(|| anyhow!("Program '{program_id}' is not deployed"))
+    }
+
+    /// Returns the public balance in microcredits associated with the address.
+    fn get_public_balance(&self, address: &Address<N>) -> Result<u64> {
+        // Initialize the program id and account identifier.
+        let credits = ProgramID::<N>::from_str("credits.aleo")?;
+        let account_mapping = Identifier::<N>::from_str("account")?;
+        let key = Plaintext::from(Literal::Address(*address));
+
+        // Return the balance in microcredits.
+        match self.get_mapping_value(&credits, &account_mapping, &key) {
+            Ok(Some(Value::Plaintext(Plaintext::Literal(Literal::<N>::U64(amount), _)))) => {
+                Ok(*amount)
+            }
+            Ok(None) => Ok(0),
+            Ok(Some(..)) => bail!("Failed to deserialize balance for {address}"),
+            Err(err) => bail!("Failed to fetch balance for {address}: {err}"),
+        }
+    }
+
+    /// Recursively loads the program and all of its imports into the process.
+    fn load_program(&self, process: &mut Process<N>, program_id: &ProgramID<N>) -> Result<()> {
+        // Return early if the program is already loaded.
+        if process.contains_program(program_id) {
+            return Ok(());
+        }
+
+        // Fetch the program.
+        let program = self.get_program(program_id)?;
+
+        // Iterate through the program imports.
+        for import_program_id in program.imports().keys() {
+            // Recursively load the imports that do not exist yet.
+            self.load_program(process, import_program_id)?;
+        }
+
+        // Add the program to the process.
+        process.add_program(&program)
+    }
+}
@@ -1,38 +1,42 @@
 use crate::*;
 pub use aleo_std::StorageMode;
+pub use anyhow::Context;
 pub use indexmap::IndexMap;
 pub use rand;
 pub use snarkvm::circuit::AleoV0;
 pub use snarkvm::console::program::*;
 pub use snarkvm::ledger::query::*;
-pub use snarkvm::ledger::store::helpers::memory::ConsensusMemory;
+pub use snarkvm::ledger::store::helpers::memory::{BlockMemory, ConsensusMemory};
 pub use snarkvm::ledger::store::ConsensusStorage;
 pub use snarkvm::ledger::store::ConsensusStore;
 
+pub use snarkvm::ledger::block::ConfirmedTransaction;
 pub use snarkvm::ledger::block::Execution;
 pub use snarkvm::ledger::block::Output;
+pub use snarkvm::ledger::block::Rejected;
 pub use snarkvm::ledger::block::Transaction;
 pub use snarkvm::ledger::block::Transition;
+pub use snarkvm::ledger::committee::Committee;
 pub use std::path::{Path, PathBuf};
 pub use std::thread::sleep;
 pub use std::time::Duration;
 pub use ureq;
 
+pub use crate::confirmation::*;
+pub use crate::execution::*;
+pub use crate::fee::*;
+pub use crate::{
+    AleoNetwork, Backend, CanaryV0, Devnet, LeologyConfig, LeologyError, LocalLedger, MainnetV0,
+    Nw, TestnetV0,
+};
+
+pub mod codegen;
+
 pub trait ToValue<N: Network> {
     fn to_value(&self) -> Value<N>;
 }
-impl ToValue<Nw> for u64 {
-    fn to_value(&self) -> Value<Nw> {
-        Value::from(Literal::U64(U64::new(*self)))
-    }
-}
-impl ToValue<Nw> for Address<Nw> {
-    fn to_value(&self) -> Value<Nw> {
-        Value::from(Literal::Address(*self))
-    }
-}
-impl ToValue<Nw> for Entry<Nw, Plaintext<Nw>> {
-    fn to_value(&self) -> Value<Nw> {
+impl<N: Network> ToValue<N> for Entry<N, Plaintext<N>> {
+    fn to_value(&self) -> Value<N> {
         match self {
             Entry::Public(entry) | Entry::Private(entry) | Entry::Constant(entry) => {
                 Value::Plaintext(entry.clone())
@@ -40,50 +44,315 @@ impl ToValue<Nw> for Entry<Nw, Plaintext<Nw>> {
         }
     }
 }
-pub trait FromValue<N: Network> {
-    fn from_value(value: Value<N>) -> Self;
+/// Converts a Rust type into a plaintext, the only values that can be nested in struct members
+/// and array elements.
+///
+/// Records and futures do not implement it, so nesting them does not compile.
+pub trait ToPlaintext<N: Network>: ToValue<N> {
+    fn to_plaintext(&self) -> Plaintext<N>;
+}
+impl<N: Network> ToValue<N> for Plaintext<N> {
+    fn to_value(&self) -> Value<N> {
+        Value::Plaintext(self.clone())
+    }
+}
+impl<N: Network> ToPlaintext<N> for Plaintext<N> {
+    fn to_plaintext(&self) -> Plaintext<N> {
+        self.clone()
+    }
+}
+impl<N: Network> ToValue<N> for Record<N, Plaintext<N>> {
+    fn to_value(&self) -> Value<N> {
+        Value::Record(self.clone())
+    }
+}
+/// Converts a value returned by the VM back into a Rust type, failing if its type does not match.
+pub trait TryFromValue<N: Network>: Sized {
+    fn try_from_value(value: Value<N>) -> Result<Self>;
+}
+/// A record of a program, wrapped by a struct declared by [`generate_bindings!`].
+pub trait ProgramRecord<N: Network>: Sized {
+    /// The ID of the program declaring the record, if the bindings were generated for one.
+    const PROGRAM_ID: Option<&'static str>;
+    /// The name of the record in the program.
+    const NAME: &'static str;
+    /// Wraps the decrypted record.
+    fn from_record(record: Record<N, Plaintext<N>>) -> Self;
+    /// Returns the decrypted record.
+    fn record(&self) -> &Record<N, Plaintext<N>>;
+    /// Returns the serial number revealed when the account, which must own the record, spends it.
+    fn serial_number(&self, account: &Account<N>) -> Result<Field<N>> {
+        let Some(program_id) = Self::PROGRAM_ID else {
+            bail!(
+                "The bindings of record '{}' were generated without a program ID",
+                Self::NAME
+            );
+        };
+        record_serial_number(
+            self.record(),
+            &ProgramID::from_str(program_id)?,
+            &Identifier::from_str(Self::NAME)?,
+            account,
+        )
+    }
+    /// Returns `true` if a transaction spending the record, owned by the account, was accepted by
+    /// the backend.
+    fn is_spent(&self, account: &Account<N>, backend: &dyn Backend<N>) -> Result<bool> {
+        backend.contains_serial_number(&self.serial_number(account)?)
+    }
 }
-impl FromValue<Nw> for Record<Nw, Plaintext<Nw>> {
-    fn from_value(value: Value<Nw>) -> Self {
+impl<N: Network> TryFromValue<N> for Record<N, Plaintext<N>> {
+    fn try_from_value(value: Value<N>) -> Result<Self> {
         match value {
-            Value::Record(value) => value,
-            _ => panic!("Wrong type."),
+            Value::Record(value) => Ok(value),
+            value => Err(unexpected_value("record", &value)),
         }
     }
 }
-impl FromValue<Nw> for u64 {
-    fn from_value(value: Value<Nw>) -> Self {
+impl<N: Network> TryFromValue<N> for Plaintext<N> {
+    fn try_from_value(value: Value<N>) -> Result<Self> {
         match value {
-            Value::Plaintext(Plaintext::Literal(Literal::U64(v), _)) => *v,
-            _ => panic!("Wrong type."),
+            Value::Plaintext(plaintext) => Ok(plaintext),
+            value => Err(unexpected_value("plaintext", &value)),
         }
     }
 }
-impl FromValue<Nw> for Address<Nw> {
-    fn from_value(value: Value<Nw>) -> Self {
+impl<N: Network> TryFromValue<N> for Future<N> {
+    fn try_from_value(value: Value<N>) -> Result<Self> {
         match value {
-            Value::Plaintext(Plaintext::Literal(Literal::Address(v), _)) => v,
-            _ => panic!("Wrong type."),
+            Value::Future(future) => Ok(future),
+            value => Err(unexpected_value("future", &value)),
         }
     }
 }
-impl FromValue<Nw> for Future<Nw> {
-    fn from_value(value: Value<Nw>) -> Self {
+impl<N: Network> ToValue<N> for Literal<N> {
+    fn to_value(&self) -> Value<N> {
+        Value::Plaintext(self.to_plaintext())
+    }
+}
+impl<N: Network> ToPlaintext<N> for Literal<N> {
+    fn to_plaintext(&self) -> Plaintext<N> {
+        Plaintext::from(self.clone())
+    }
+}
+impl<N: Network> TryFromValue<N> for Literal<N> {
+    fn try_from_value(value: Value<N>) -> Result<Self> {
         match value {
-            Value::Future(future) => future,
-            _ => panic!("Wrong type."),
+            Value::Plaintext(Plaintext::Literal(literal, _)) => Ok(literal),
+            value => Err(unexpected_value("literal", &value)),
         }
     }
 }
 
+/// Implements `ToValue`, `ToPlaintext` and `TryFromValue` for the console type of a literal variant.
+macro_rules! impl_literal_value {
+    ($($variant:ident($console:ident)),* $(,)?) => {
+        $(impl<N: Network> ToValue<N> for $console<N> {
+            fn to_value(&self) -> Value<N> {
+                Value::Plaintext(self.to_plaintext())
+            }
+        }
+        impl<N: Network> ToPlaintext<N> for $console<N> {
+            fn to_plaintext(&self) -> Plaintext<N> {
+                Plaintext::from(Literal::$variant(*self))
+            }
+        }
+        impl<N: Network> TryFromValue<N> for $console<N> {
+            fn try_from_value(value: Value<N>) -> Result<Self> {
+                match value {
+                    Value::Plaintext(Plaintext::Literal(Literal::$variant(v), _)) => Ok(v),
+                    value => Err(unexpected_value(&LiteralType::$variant.to_string(), &value)),
+                }
+            }
+        })*
+    };
+}
+impl_literal_value!(
+    Address(Address),
+    Boolean(Boolean),
+    Field(Field),
+    Group(Group),
+    I8(I8),
+    I16(I16),
+    I32(I32),
+    I64(I64),
+    I128(I128),
+    U8(U8),
+    U16(U16),
+    U32(U32),
+    U64(U64),
+    U128(U128),
+    Scalar(Scalar),
+);
+
+/// Implements `ToValue`, `ToPlaintext` and `TryFromValue` for a native Rust type, through the console type of a literal variant.
+macro_rules! impl_native_value {
+    ($($variant:ident($native:ty, $console:ident)),* $(,)?) => {
+        $(impl<N: Network> ToValue<N> for $native {
+            fn to_value(&self) -> Value<N> {
+                Value::Plaintext(self.to_plaintext())
+            }
+        }
+        impl<N: Network> ToPlaintext<N> for $native {
+            fn to_plaintext(&self) -> Plaintext<N> {
+                Plaintext::from(Literal::$variant($console::new(*self)))
+            }
+        }
+        impl<N: Network> TryFromValue<N> for $native {
+            fn try_from_value(value: Value<N>) -> Result<Self> {
+                match value {
+                    Value::Plaintext(Plaintext::Literal(Literal::$variant(v), _)) => Ok(*v),
+                    value => Err(unexpected_value(&LiteralType::$variant.to_string(), &value)),
+                }
+            }
+        })*
+    };
+}
+impl_native_value!(
+    Boolean(bool, Boolean),
+    I8(i8, I8),
+    I16(i16, I16),
+    I32(i32, I32),
+    I64(i64, I64),
+    I128(i128, I128),
+    U8(u8, U8),
+    U16(u16, U16),
+    U32(u32, U32),
+    U64(u64, U64),
+    U128(u128, U128),
+);
+
+impl<N: Network> ToValue<N> for Signature<N> {
+    fn to_value(&self) -> Value<N> {
+        Value::Plaintext(self.to_plaintext())
+    }
+}
+impl<N: Network> ToPlaintext<N> for Signature<N> {
+    fn to_plaintext(&self) -> Plaintext<N> {
+        Plaintext::from(Literal::Signature(Box::new(*self)))
+    }
+}
+impl<N: Network> TryFromValue<N> for Signature<N> {
+    fn try_from_value(value: Value<N>) -> Result<Self> {
+        match value {
+            Value::Plaintext(Plaintext::Literal(Literal::Signature(v), _)) => Ok(*v),
+            value => Err(unexpected_value("signature", &value)),
+        }
+    }
+}
+impl<N: Network> ToValue<N> for StringType<N> {
+    fn to_value(&self) -> Value<N> {
+        Value::Plaintext(self.to_plaintext())
+    }
+}
+impl<N: Network> ToPlaintext<N> for StringType<N> {
+    fn to_plaintext(&self) -> Plaintext<N> {
+        Plaintext::from(Literal::String(self.clone()))
+    }
+}
+impl<N: Network> TryFromValue<N> for StringType<N> {
+    fn try_from_value(value: Value<N>) -> Result<Self> {
+        match value {
+            Value::Plaintext(Plaintext::Literal(Literal::String(v), _)) => Ok(v),
+            value => Err(unexpected_value("string", &value)),
+        }
+    }
+}
+impl<N: Network, T: ToPlaintext<N>, const LEN: usize> ToValue<N> for [T; LEN] {
+    fn to_value(&self) -> Value<N> {
+        Value::Plaintext(self.to_plaintext())
+    }
+}
+impl<N: Network, T: ToPlaintext<N>, const LEN: usize> ToPlaintext<N> for [T; LEN] {
+    fn to_plaintext(&self) -> Plaintext<N> {
+        let elements = self.iter().map(ToPlaintext::to_plaintext).collect();
+        Plaintext::Array(elements, Default::default())
+    }
+}
+impl<N: Network, T: TryFromValue<N>, const LEN: usize> TryFromValue<N> for [T; LEN] {
+    fn try_from_value(value: Value<N>) -> Result<Self> {
+        match value {
+            Value::Plaintext(Plaintext::Array(elements, _)) if elements.len() == LEN => {
+                let elements = elements
+                    .into_iter()
+                    .enumerate()
+                    .map(|(index, element)| {
+                        T::try_from_value(Value::Plaintext(element))
+                            .with_context(|| format!("Failed to decode array element {index}"))
+                    })
+                    .collect::<Result<Vec<T>>>()?;
+                match elements.try_into() {
+                    Ok(array) => Ok(array),
+                    Err(_) => unreachable!("The array length was checked."),
+                }
+            }
+            value => Err(unexpected_value(
+                &format!("array of {LEN} elements"),
+                &value,
+            )),
+        }
+    }
+}
+
+/// Returns the Aleo name of an identifier stringified by the bindings macros, without the `r#`
+/// prefix of the names that are Rust keywords.
+pub fn aleo_name(name: &str) -> &str {
+    name.strip_prefix("r#").unwrap_or(name)
+}
+
+/// Returns `true` if the entries of the record are named `entries`, in order.
+///
+/// A plaintext record does not tell which record type it was created as, so the bindings tell
+/// record types apart by their entries.
+pub fn has_entries<N: Network>(record: &Record<N, Plaintext<N>>, entries: &[&str]) -> bool {
+    record.data().len() == entries.len()
+        && record
+            .data()
+            .keys()
+            .zip(entries)
+            .all(|(name, entry)| name.to_string() == *entry)
+}
+
+/// Returns the error raised when converting a value that is not of the `expected` type.
+pub fn unexpected_value<N: Network>(expected: &str, value: &Value<N>) -> Error {
+    LeologyError::UnexpectedValue {
+        expected: expected.to_string(),
+        found: describe_value(value),
+    }
+    .into()
+}
+
+/// Describes the type of a value, for error messages.
+pub fn describe_value<N: Network>(value: &Value<N>) -> String {
+    match value {
+        Value::Plaintext(Plaintext::Literal(literal, _)) => literal.to_type().to_string(),
+        Value::Plaintext(Plaintext::Struct(members, _)) => {
+            let members: Vec<String> = members.keys().map(ToString::to_string).collect();
+            format!("struct with members ({})", members.join(", "))
+        }
+        Value::Plaintext(Plaintext::Array(elements, _)) => {
+            format!("array of {} elements", elements.len())
+        }
+        Value::Record(record) => {
+            let entries: Vec<String> = record.data().keys().map(ToString::to_string).collect();
+            format!("record with entries ({})", entries.join(", "))
+        }
+        Value::Future(future) => format!(
+            "future of '{}/{}'",
+            future.program_id(),
+            future.function_name()
+        ),
+    }
+}
+
 /// A helper function to recursively load the program and all of its imports into the process.
-pub fn load_program(
-    endpoint: &str,
-    process: &mut Process<Nw>,
-    program_id: &ProgramID<Nw>,
+pub fn load_program<N: Network>(
+    config: &LeologyConfig,
+    process: &mut Process<N>,
+    program_id: &ProgramID<N>,
 ) -> Result<()> {
     // Fetch the program.
-    let program = fetch_program(program_id, endpoint)?;
+    let program = fetch_program(program_id, config)?;
 
     // Return early if the program is already loaded.
     if process.contains_program(program.id()) {
@@ -95,7 +364,7 @@ pub fn load_program(
         // Add the imports to the process if does not exist yet.
         if !process.contains_program(import_program_id) {
             // Recursively load the program and its imports.
-            load_program(endpoint, process, import_program_id)?;
+            load_program(config, process, import_program_id)?;
         }
     }
 
@@ -106,12 +375,54 @@ pub fn load_program(
 
     Ok(())
 }
-/// Fetch the program from the given endpoint.
-pub fn fetch_program(program_id: &ProgramID<Nw>, endpoint: &str) -> Result<Program<Nw>> {
+/// Fetch the program from the configured endpoint.
+pub fn fetch_program<N: Network>(
+    program_id: &ProgramID<N>,
+    config: &LeologyConfig,
+) -> Result<Program<N>> {
+    find_program(program_id, config)?.ok_or_else(|| {
+        anyhow!(
+            "Program '{program_id}' is not deployed to {}",
+            config.endpoint
+        )
+    })
+}
+/// Fetch the program from the configured endpoint, if it is deployed.
+pub fn find_program<N: Network>(
+    program_id: &ProgramID<N>,
+    config: &LeologyConfig,
+) -> Result<Option<Program<N>>> {
     // Send a request to the query node.
-    let response = ureq::get("http://127.0.0.1:3030/testnet/program/dev.aleo").call();
+    let response = ureq::get(&config.url(format_args!("program/{program_id}"))).call();
 
-    // Deserialize the program.
+    // snarkOS reports a missing program as an error, whose message tells it apart from other failures.
+    match response {
+        Ok(response) => response.into_json().map_err(|err| err.into()),
+        Err(ureq::Error::Status(404, _)) => Ok(None),
+        Err(ureq::Error::Status(_status, response)) => {
+            let message = response
+                .into_string()
+                .unwrap_or("Response too large!".to_owned());
+            if message.contains("Missing program for ID") {
+                Ok(None)
+            } else {
+                bail!(message)
+            }
+        }
+        Err(err) => bail!(err),
+    }
+}
+/// Fetch the value stored under `key` in the program's mapping from the configured endpoint.
+pub fn get_mapping_value<N: Network>(
+    program_id: &ProgramID<N>,
+    mapping_name: &Identifier<N>,
+    key: &Plaintext<N>,
+    config: &LeologyConfig,
+) -> Result<Option<Value<N>>> {
+    // Send a request to the query node.
+    let response = ureq::get(&mapping_value_url(program_id, mapping_name, key, config)).call();
+
+    // Deserialize the value.
     match response {
         Ok(response) => response.into_json().map_err(|err| err.into()),
         Err(err) => match err {
@@ -124,20 +435,39 @@ pub fn fetch_program(program_id: &ProgramID<Nw>, endpoint: &str) -> Result<Progr
         },
     }
 }
-/// Fetch the public balance in microcredits associated with the address from the given endpoint.
-pub fn get_public_balance(address: &Address<Nw>, endpoint: &str) -> Result<u64> {
-    // Initialize the program id and account identifier.
-    let credits = ProgramID::<Nw>::from_str("credits.aleo")?;
-    let account_mapping = Identifier::<Nw>::from_str("account")?;
+/// Returns the URL of the value stored under `key` in the program's mapping.
+///
+/// Struct and array keys contain spaces, braces and brackets, so the key is percent-encoded.
+fn mapping_value_url<N: Network>(
+    program_id: &ProgramID<N>,
+    mapping_name: &Identifier<N>,
+    key: &Plaintext<N>,
+    config: &LeologyConfig,
+) -> String {
+    let key = key.to_string();
+    let key = percent_encoding::utf8_percent_encode(&key, percent_encoding::NON_ALPHANUMERIC);
+    config.url(format_args!(
+        "program/{program_id}/mapping/{mapping_name}/{key}"
+    ))
+}
+/// Fetch the height of the latest block from the configured endpoint.
+pub fn fetch_latest_height(config: &LeologyConfig) -> Result<u32> {
+    // Send a request to the query node.
+    let response = ureq::get(&config.url("block/height/latest")).call();
 
+    // Deserialize the height.
+    match response {
+        Ok(response) => response.into_json().map_err(|err| err.into()),
+        Err(err) => bail!(err),
+    }
+}
+/// Fetch the block at the given height from the configured endpoint.
+pub fn fetch_block_at_height<N: Network>(height: u32, config: &LeologyConfig) -> Result<Block<N>> {
     // Send a request to the query node.
-    let response = ureq::get(&format!(
-        "{endpoint}/testnet/program/{credits}/mapping/{account_mapping}/{address}"
-    ))
-    .call();
+    let response = ureq::get(&config.url(format_args!("block/{height}"))).call();
 
-    // Deserialize the balance.
-    let balance: Result<Option<Value<Nw>>> = match response {
+    // Deserialize the block.
+    match response {
         Ok(response) => response.into_json().map_err(|err| err.into()),
         Err(err) => match err {
             ureq::Error::Status(_status, response) => {
@@ -147,44 +477,98 @@ pub fn get_public_balance(address: &Address<Nw>, endpoint: &str) -> Result<u64>
             }
             err => bail!(err),
         },
-    };
+    }
+}
+/// Check whether the configured endpoint knows a transition spending the given serial number.
+pub fn contains_serial_number<N: Network>(
+    serial_number: &Field<N>,
+    config: &LeologyConfig,
+) -> Result<bool> {
+    // Record inputs are identified by their serial number.
+    let response = ureq::get(&config.url(format_args!("find/transitionID/{serial_number}"))).call();
 
-    // Return the balance in microcredits.
-    match balance {
-        Ok(Some(Value::Plaintext(Plaintext::Literal(Literal::<Nw>::U64(amount), _)))) => {
-            Ok(*amount)
+    // snarkOS reports an unknown ID as an error, whose message tells it apart from other failures.
+    match response {
+        Ok(_) => Ok(true),
+        Err(ureq::Error::Status(404, _)) => Ok(false),
+        Err(ureq::Error::Status(_status, response)) => {
+            let message = response
+                .into_string()
+                .unwrap_or("Response too large!".to_owned());
+            if message.contains("Failed to find the transition ID") {
+                Ok(false)
+            } else {
+                bail!(message)
+            }
         }
-        Ok(None) => Ok(0),
-        Ok(Some(..)) => bail!("Failed to deserialize balance for {address}"),
-        Err(err) => bail!("Failed to fetch balance for {address}: {err}"),
+        Err(err) => bail!(err),
+    }
+}
+/// Fetch the latest state root from the configured endpoint.
+pub fn fetch_state_root<N: Network>(config: &LeologyConfig) -> Result<<N as Network>::StateRoot> {
+    // Send a request to the query node.
+    let response = ureq::get(&config.url("stateRoot/latest")).call();
+
+    // Deserialize the state root.
+    match response {
+        Ok(response) => response.into_json().map_err(|err| err.into()),
+        Err(err) => bail!(err),
     }
 }
+/// Fetch the latest committee of validators from the configured endpoint.
+pub fn fetch_committee<N: Network>(config: &LeologyConfig) -> Result<Committee<N>> {
+    // Send a request to the query node.
+    let response = ureq::get(&config.url("committee/latest")).call();
 
-pub fn initialize_vm() -> Result<VM<Nw, ConsensusMemory<Nw>>> {
-    let store = ConsensusStore::<Nw, ConsensusMemory<Nw>>::open(Some(3))?;
+    // Deserialize the committee.
+    match response {
+        Ok(response) => response.into_json().map_err(|err| err.into()),
+        Err(err) => bail!(err),
+    }
+}
+/// Fetch the number of peers connected to the node at the configured endpoint.
+pub fn fetch_peer_count(config: &LeologyConfig) -> Result<usize> {
+    // Send a request to the query node.
+    let response = ureq::get(&config.url("peers/count")).call();
+
+    // Deserialize the count.
+    match response {
+        Ok(response) => response.into_json().map_err(|err| err.into()),
+        Err(err) => bail!(err),
+    }
+}
+/// Fetch the public balance in microcredits associated with the address from the configured endpoint.
+pub fn get_public_balance<N: AleoNetwork>(
+    address: &Address<N>,
+    config: &LeologyConfig,
+) -> Result<u64> {
+    Backend::<N>::get_public_balance(&Devnet::with_config(config.clone()), address)
+}
+
+pub fn initialize_vm<N: Network>() -> Result<VM<N, ConsensusMemory<N>>> {
+    let store = ConsensusStore::<N, ConsensusMemory<N>>::open(Some(3))?;
     let vm = VM::from(store)?;
     Ok(vm)
 }
-pub fn broadcast_transaction(transaction: Transaction<Nw>) -> Result<String> {
+/// Broadcast the transaction to the configured endpoint, and wait for it to be included in a block.
+pub fn broadcast_transaction<N: Network>(
+    transaction: Transaction<N>,
+    config: &LeologyConfig,
+) -> Result<Confirmation<N>> {
+    let endpoint = &config.endpoint;
     let transaction_id = transaction.id();
     ensure!(
         !transaction.is_fee(),
         "The transaction is a fee transaction and cannot be broadcast"
     );
     // Send the deployment request to the local development node.
-    match ureq::post("http://127.0.0.1:3030/testnet/transaction/broadcast").send_json(&transaction)
-    {
+    match ureq::post(&config.url("transaction/broadcast")).send_json(&transaction) {
         Ok(id) => {
-            dbg!(&id);
             // Remove the quotes from the response.
             let response_string = id.into_string()?.trim_matches('\"').to_string();
             ensure!( response_string == transaction_id.to_string(), "The response does not match the transaction id. ({response_string} != {transaction_id})");
-            sleep(Duration::from_secs(40));
-            println!(
-                "⌛ Execution {transaction_id} has been broadcast to {}.",
-                DEFAULT_ENDPOINT
-            );
-            Ok(response_string)
+            println!("⌛ Execution {transaction_id} has been broadcast to {endpoint}.");
+            wait_for_confirmation(&transaction_id, config)
         }
         Err(error) => {
             let error_message = match error {
@@ -193,176 +577,703 @@ pub fn broadcast_transaction(transaction: Transaction<Nw>) -> Result<String> {
                 }
                 ureq::Error::Transport(err) => format!("({err})"),
             };
-            bail!(
-                "❌ Failed to broadcast execution to {}: {}",
-                DEFAULT_ENDPOINT,
-                error_message
-            )
-        }
-    }
-}
-pub fn make_outputs(execution: &Execution<Nw>, account: Account<Nw>) {
-    let mut transitions = execution.transitions();
-    let outputs_iter = transitions.next().unwrap().outputs().iter();
-    let outputs: Vec<Value<Nw>> = outputs_iter
-        .filter_map(|output| match output {
-            Output::Constant(_, plaintext) | Output::Public(_, plaintext) => {
-                Some(Value::Plaintext(plaintext.clone().unwrap()))
-            }
-            Output::Record(_, _, record_ciphertext) => Some(Value::Record(
-                record_ciphertext
-                    .clone()
-                    .unwrap()
-                    .decrypt(account.view_key())
-                    .unwrap(),
-            )),
-            Output::Future(_, future) => Some(Value::Future(future.clone().unwrap())),
-            _ => None,
-        })
-        .collect();
+            bail!("❌ Failed to broadcast execution to {endpoint}: {error_message}")
+        }
+    }
+}
+/// Deploys the compiled program of the package to the backend of the wallet, paying the fee from
+/// the wallet's account with the given strategy.
+pub fn deploy_package<N: AleoNetwork>(
+    wallet: &Wallet<N>,
+    package: &Package<N>,
+    fee_strategy: &FeeStrategy<N>,
+) -> Result<Confirmation<N>> {
+    let deployer = wallet.account();
+    let backend = wallet.backend();
+    let deployment: Deployment<N> = package.deploy::<N::Circuit>(None)?;
+    let deployment_id = deployment.to_deployment_id()?;
+
+    let transaction = {
+        let rng = &mut rand::thread_rng();
+        let store = ConsensusStore::<N, ConsensusMemory<N>>::open(0u16)?;
+        let vm = VM::from(store)?;
+        let (minimum_deployment_cost, (_, _, _)) = deployment_cost(&deployment)?;
+        let fee_authorization =
+            fee_strategy.authorize(&vm, wallet, minimum_deployment_cost, 0, deployment_id, rng)?;
+        let fee = vm.execute_fee_authorization(fee_authorization, Some(backend.query()), rng)?;
+        let owner = ProgramOwner::new(deployer.private_key(), deployment_id, rng)?;
+
+        Transaction::from_deployment(owner, deployment, fee)?
+    };
+    println!(
+        "✅ Created deployment transaction for '{}'",
+        package.program_id()
+    );
+    let confirmation = backend
+        .broadcast_transaction(transaction)?
+        .ensure_accepted()?;
+    println!("Result of broadcast deployment: {confirmation}");
+    Ok(confirmation)
+}
+/// Returns the serial number of a record of the program, revealed when the account spends it.
+///
+/// The serial number is derived from the account's private key and the commitment of the record,
+/// so it can only be computed by the owner.
+pub fn record_serial_number<N: Network>(
+    record: &Record<N, Plaintext<N>>,
+    program_id: &ProgramID<N>,
+    record_name: &Identifier<N>,
+    account: &Account<N>,
+) -> Result<Field<N>> {
+    ensure!(
+        **record.owner() == account.address(),
+        "The record is owned by {}, not by {}",
+        **record.owner(),
+        account.address()
+    );
+    let commitment = record.to_commitment(program_id, record_name)?;
+    Record::<N, Plaintext<N>>::serial_number(*account.private_key(), commitment)
+}
+/// Decodes the outputs of the invoked function from the transitions of an execution.
+pub fn make_outputs<N: Network>(
+    transaction: &Transaction<N>,
+    locator: &Locator<N>,
+    account: &Account<N>,
+) -> Result<Vec<Value<N>>> {
+    ExecutionReport::decode(transaction, account.view_key())?.outputs(locator)
+}
+/// Declares a Rust struct mirroring an Aleo struct, convertible to and from `Plaintext::Struct`.
+///
+/// Structs holding network-specific types, such as addresses, take the network as a type
+/// parameter: `generate_struct! { Owner<N> { address: Address<N> } }`.
+#[macro_export]
+macro_rules! generate_struct {
+    (@impl $struct_name:ident, $self_type:ty, $network:ident, { $($member:ident : $member_type:ty),* }) => {
+        impl<$network: leology::Network> leology::bindings::ToValue<$network> for $self_type {
+            fn to_value(&self) -> leology::bindings::Value<$network> {
+                leology::bindings::Value::Plaintext(leology::bindings::ToPlaintext::to_plaintext(self))
+            }
+        }
+        impl<$network: leology::Network> leology::bindings::ToPlaintext<$network> for $self_type {
+            fn to_plaintext(&self) -> leology::bindings::Plaintext<$network> {
+                use leology::bindings::{Identifier, IndexMap, Plaintext, ToPlaintext};
+                let mut members = IndexMap::new();
+                $(members.insert(
+                    Identifier::try_from(leology::bindings::aleo_name(stringify!($member))).unwrap(),
+                    ToPlaintext::<$network>::to_plaintext(&self.$member),
+                );)*
+                Plaintext::Struct(members, Default::default())
+            }
+        }
+        impl<$network: leology::Network> leology::bindings::TryFromValue<$network> for $self_type {
+            fn try_from_value(value: leology::bindings::Value<$network>) -> leology::Result<Self> {
+                use leology::bindings::{unexpected_value, Identifier, Plaintext, Value};
+                use leology::bindings::Context;
+                match value {
+                    Value::Plaintext(Plaintext::Struct(mut members, _)) => Ok(Self {
+                        $($member: {
+                            let member = Identifier::try_from(leology::bindings::aleo_name(stringify!($member)))?;
+                            let plaintext = members.swap_remove(&member).ok_or_else(|| {
+                                leology::anyhow!(
+                                    "Struct '{}' has no member '{}'",
+                                    stringify!($struct_name),
+                                    member
+                                )
+                            })?;
+                            <$member_type as leology::bindings::TryFromValue<$network>>::try_from_value(
+                                Value::Plaintext(plaintext),
+                            )
+                            .with_context(|| format!(
+                                "Failed to decode member '{}' of struct '{}'",
+                                member,
+                                stringify!($struct_name)
+                            ))?
+                        }),*
+                    }),
+                    value => Err(unexpected_value(
+                        &format!("struct '{}'", stringify!($struct_name)),
+                        &value,
+                    )),
+                }
+            }
+        }
+    };
+    ($struct_name:ident<$network:ident> { $($member:ident : $member_type:ty),* $(,)? }) => {
+        #[derive(Clone, Debug, PartialEq)]
+        pub struct $struct_name<$network: leology::Network = leology::Nw> {
+            $(pub $member: $member_type),*
+        }
+        leology::generate_struct!(@impl $struct_name, $struct_name<$network>, $network, { $($member: $member_type),* });
+    };
+    ($struct_name:ident { $($member:ident : $member_type:ty),* $(,)? }) => {
+        #[derive(Clone, Debug, PartialEq)]
+        pub struct $struct_name {
+            $(pub $member: $member_type),*
+        }
+        leology::generate_struct!(@impl $struct_name, $struct_name, N, { $($member: $member_type),* });
+    };
 }
 #[macro_export]
 macro_rules! generate_bindings {
-    ($program_name:ident, {
-        [$({$function_name:ident, ($($input_name:ident : $input_type:ty),*), ($($output_type:ty),*)},)*],
-        [{$($record_name:ident, ($($record_field:ident : $record_field_type:ty),*) );*}],
+    (@aleo_name $name:ident) => {
+        aleo_name(stringify!($name))
+    };
+    (@aleo_name $name:ident $aleo_name:literal) => {
+        $aleo_name
+    };
+    ($program_name:ident $(($program_id:literal))?, {
+        [$({$function_name:ident $(($function_id:literal))?, ($($input_name:ident : $input_type:ty),*), ($($output_type:ty),*)},)*],
+        [{$($record_name:ident ($record_id:literal), ($($record_field:ident $(($record_field_id:literal))? : $record_field_type:ty),*) );*}],
+        $([$({$mapping_name:ident $(($mapping_id:literal))?, $mapping_key_type:ty => $mapping_value_type:ty},)*],)?
     }) => {
         use leology::bindings::*;
-        use leology::Aleo;
-        #[derive(Debug)]
-        $(pub struct $record_name {
-            pub record: Record<Nw, Plaintext<Nw>>,
+        $(#[derive(Clone, Debug)]
+        pub struct $record_name<N: Network = Nw> {
+            pub record: Record<N, Plaintext<N>>,
         }
-        impl ToValue<Nw> for $record_name {
-            fn to_value(&self) -> Value<Nw> {
+        impl<N: Network> ToValue<N> for $record_name<N> {
+            fn to_value(&self) -> Value<N> {
                 Value::Record(self.record.clone())
             }
         }
-        impl FromValue<Nw> for $record_name {
-            fn from_value(value: Value<Nw>) -> Self {
+        impl<N: Network> TryFromValue<N> for $record_name<N> {
+            fn try_from_value(value: Value<N>) -> Result<Self> {
+                let entries = [$(leology::generate_bindings!(@aleo_name $record_field $($record_field_id)?)),*];
                 match value {
-                    Value::Record(record) => {
-                        Self{record}
-                    },
-                    _ => panic!(),
+                    Value::Record(record) if has_entries(&record, &entries) => Ok(Self { record }),
+                    value => Err(unexpected_value(
+                        &format!("record '{}' with entries ({})", stringify!($record_name), entries.join(", ")),
+                        &value,
+                    )),
                 }
             }
         }
-        impl $record_name {
-            pub fn new(record: Record<Nw, Plaintext<Nw>>) -> Self {
+        impl<N: Network> ProgramRecord<N> for $record_name<N> {
+            const PROGRAM_ID: Option<&'static str> = $program_name::<Nw>::PROGRAM_ID;
+            const NAME: &'static str = $record_id;
+            fn from_record(record: Record<N, Plaintext<N>>) -> Self {
+                Self { record }
+            }
+            fn record(&self) -> &Record<N, Plaintext<N>> {
+                &self.record
+            }
+        }
+        impl<N: Network> $record_name<N> {
+            pub fn new(record: Record<N, Plaintext<N>>) -> Self {
                 $record_name { record }
             }
-            $(pub fn $record_field(&self) -> $record_field_type {
-                let field = &Identifier::try_from(stringify!($record_field)).unwrap();
-                let entry = self.record.data()
-                    .get(field).unwrap();
-                let value = entry.to_value();
-                <$record_field_type>::from_value(value)
+            $(pub fn $record_field(&self) -> Result<$record_field_type> {
+                let field = Identifier::try_from(leology::generate_bindings!(@aleo_name $record_field $($record_field_id)?))?;
+                let entry = self.record.data().get(&field).ok_or_else(|| {
+                    anyhow!("Record '{}' has no field '{}'", stringify!($record_name), field)
+                })?;
+                <$record_field_type as TryFromValue<N>>::try_from_value(entry.to_value()).with_context(|| {
+                    format!("Failed to decode field '{}' of record '{}'", field, stringify!($record_name))
+                })
             })*
         })*
-        pub struct $program_name {
-            pub package: Package<Nw>,
-        }
-        impl $program_name {
-            pub fn new(deployer: &Account<Nw>) -> Result<Self> {
-                let query = Query::from(DEFAULT_ENDPOINT);
-                let package = Package::open("build".as_ref())?;
-                let deployment: Deployment<Nw> = package.deploy::<Aleo>(None)
-                    .expect("Error in package.deploy.");
-                let deployment_id = deployment.to_deployment_id()?;
-
-                let transaction = {
+        pub struct $program_name<N: AleoNetwork = Nw> {
+            pub program: Program<N>,
+            pub program_id: ProgramID<N>,
+            pub backend: std::sync::Arc<dyn Backend<N>>,
+            pub fee_strategy: FeeStrategy<N>,
+            pub priority_fee: u64,
+            /// The wallets of the accounts that paid fees, kept to only scan new blocks for fee records.
+            fee_wallets: std::sync::Mutex<Vec<std::sync::Arc<Wallet<N>>>>,
+        }
+        impl<N: AleoNetwork> $program_name<N> {
+            /// The ID of the program the bindings were generated for, if any.
+            pub const PROGRAM_ID: Option<&'static str> = {
+                #[allow(unused_mut, unused_assignments)]
+                let mut program_id = None;
+                $(program_id = Some($program_id);)?
+                program_id
+            };
+            /// Deploys the program in the `build` folder of the current directory to the given backend.
+            pub fn deploy(deployer: &Account<N>, backend: impl Backend<N> + 'static) -> Result<Self> {
+                Self::from_directory(deployer, SNARKVM_CONTRACTS_BUILD_FOLDER, backend, FeeStrategy::default())
+            }
+            /// Binds to the program with the given ID, already deployed to the given backend.
+            pub fn attach(
+                program_id: impl TryInto<ProgramID<N>>,
+                backend: impl Backend<N> + 'static,
+            ) -> Result<Self> {
+                let program_id = program_id.try_into().map_err(|_| anyhow!("Invalid program ID"))?;
+                Self::ensure_program_id(&program_id)?;
+                let program = backend.get_program(&program_id)?;
+                Ok(Self::bind(program, std::sync::Arc::new(backend), FeeStrategy::default()))
+            }
+            /// Binds to the program in the `build` folder of the current directory if the backend
+            /// already holds the same bytecode, and deploys it otherwise.
+            pub fn deploy_or_attach(deployer: &Account<N>, backend: impl Backend<N> + 'static) -> Result<Self> {
+                let package = Package::<N>::open(Path::new(SNARKVM_CONTRACTS_BUILD_FOLDER))?;
+                Self::ensure_program_id(package.program_id())?;
+                match backend.find_program(package.program_id())? {
+                    Some(program) if &program == package.program() => {
+                        println!("♻️ Reusing the deployment of '{}'", program.id());
+                        Ok(Self::bind(program, std::sync::Arc::new(backend), FeeStrategy::default()))
+                    }
+                    Some(program) => bail!(
+                        "'{}' is already deployed with a different bytecode than '{}'",
+                        program.id(),
+                        Path::new(SNARKVM_CONTRACTS_BUILD_FOLDER).join("main.aleo").display()
+                    ),
+                    None => Self::deploy(deployer, backend),
+                }
+            }
+            /// Deploys the compiled program in the given directory to the given backend, paying the
+            /// deployment fee and the fees of later executions with the given strategy.
+            pub fn from_directory(
+                deployer: &Account<N>,
+                directory: impl AsRef<Path>,
+                backend: impl Backend<N> + 'static,
+                fee_strategy: FeeStrategy<N>,
+            ) -> Result<Self> {
+                let backend: std::sync::Arc<dyn Backend<N>> = std::sync::Arc::new(backend);
+                let package = Package::open(directory.as_ref())?;
+                Self::ensure_program_id(package.program_id())?;
+                let wallet = Wallet::new(deployer.clone(), backend.clone());
+                deploy_package(&wallet, &package, &fee_strategy)?;
+                let bindings = Self::bind(package.program().clone(), backend, fee_strategy);
+                bindings.fee_wallets.lock().unwrap().push(std::sync::Arc::new(wallet));
+                Ok(bindings)
+            }
+            /// Builds the handle of a deployed program.
+            fn bind(program: Program<N>, backend: std::sync::Arc<dyn Backend<N>>, fee_strategy: FeeStrategy<N>) -> Self {
+                let program_id = *program.id();
+                let fee_wallets = std::sync::Mutex::new(Vec::new());
+                Self { program, program_id, backend, fee_strategy, priority_fee: 0, fee_wallets }
+            }
+            /// Returns the wallet tracking the records the account can pay fees with.
+            fn fee_wallet(&self, account: &Account<N>) -> std::sync::Arc<Wallet<N>> {
+                let mut fee_wallets = self.fee_wallets.lock().unwrap();
+                match fee_wallets.iter().find(|wallet| wallet.account().address() == account.address()) {
+                    Some(wallet) => wallet.clone(),
+                    None => {
+                        let wallet = std::sync::Arc::new(Wallet::new(account.clone(), self.backend.clone()));
+                        fee_wallets.push(wallet.clone());
+                        wallet
+                    }
+                }
+            }
+            /// Ensures the bindings were generated for the program with the given ID.
+            fn ensure_program_id(program_id: &ProgramID<N>) -> Result<()> {
+                if let Some(expected) = Self::PROGRAM_ID {
+                    ensure!(
+                        *program_id == ProgramID::<N>::from_str(expected)?,
+                        "The program is '{}', but the bindings were generated for '{}'",
+                        program_id,
+                        expected
+                    );
+                }
+                Ok(())
+            }
+            /// Sets how the fees of later executions are paid.
+            pub fn with_fee_strategy(mut self, fee_strategy: FeeStrategy<N>) -> Self {
+                self.fee_strategy = fee_strategy;
+                self
+            }
+            /// Returns the ID of the bound program.
+            pub fn program_id(&self) -> &ProgramID<N> {
+                &self.program_id
+            }
+            $($(
+            /// Returns the value stored under `key` in the mapping, if any.
+            pub fn $mapping_name(&self, key: &$mapping_key_type) -> Result<Option<$mapping_value_type>> {
+                let mapping_name = Identifier::from_str(leology::generate_bindings!(@aleo_name $mapping_name $($mapping_id)?))?;
+                let key = match ToValue::<N>::to_value(key) {
+                    Value::Plaintext(key) => key,
+                    _ => bail!("The key of mapping '{}' must be a plaintext", mapping_name),
+                };
+                let value = self.backend.get_mapping_value(&self.program_id, &mapping_name, &key)?;
+                value
+                    .map(<$mapping_value_type as TryFromValue<N>>::try_from_value)
+                    .transpose()
+                    .with_context(|| format!("Failed to decode the value of mapping '{}'", mapping_name))
+            }
+            )*)?
+            /// Sets the priority fee in microcredits paid by later executions.
+            pub fn with_priority_fee(mut self, priority_fee: u64) -> Self {
+                self.priority_fee = priority_fee;
+                self
+            }
+            /// Estimates the fee of executing a function of the program with the given inputs,
+            /// without broadcasting it.
+            pub fn estimate_fee(
+                &self,
+                account: &Account<N>,
+                function_name: &str,
+                args: &[Value<N>],
+            ) -> Result<FeeEstimate> {
+                let (vm, execution, height, _) = self.prepare_execution(account, function_name, args)?;
+                FeeEstimate::for_execution(&vm.process().read(), &execution, height, self.priority_fee)
+            }
+            /// Executes a function of the program with the given inputs, paying the fee with the
+            /// given strategy and priority fee, and decodes every transition of the resulting execution.
+            pub fn execute_with_report(
+                &self,
+                account: &Account<N>,
+                function_name: &str,
+                args: &[Value<N>],
+                fee_strategy: &FeeStrategy<N>,
+                priority_fee: u64,
+            ) -> Result<ExecutionReport<N>> {
+                let locator = Locator::<N>::new(self.program_id, Identifier::from_str(function_name)?);
+                println!("Transaction of function {}:", function_name);
+                let (transaction, response) = {
                     let rng = &mut rand::thread_rng();
-                    let store = ConsensusStore::<Nw, ConsensusMemory<Nw>>::open(0u16)?;
-                    let vm = VM::from(store)?;
-                    let (minimum_deployment_cost, (_, _, _)) = deployment_cost(&deployment)?;
-                    let fee_authorization = vm.authorize_fee_public(
-                        deployer.private_key(),
-                        minimum_deployment_cost,
-                        0,
-                        deployment_id,
+                    let (vm, execution, height, response) = self.prepare_execution(account, function_name, args)?;
+                    let estimate = FeeEstimate::for_execution(&vm.process().read(), &execution, height, priority_fee)?;
+                    println!("💰 Estimated fee for '{}': {}", locator, estimate);
+                    let fee_authorization = fee_strategy.authorize(
+                        &vm,
+                        &self.fee_wallet(account),
+                        estimate.base_fee(),
+                        estimate.priority_fee,
+                        execution.to_execution_id()?,
                         rng,
-                        )?;
-                    let fee = vm.execute_fee_authorization(fee_authorization, Some(query), rng)?;
-                    let owner = ProgramOwner::new(deployer.private_key(), deployment_id, rng)?;
-
-                    Transaction::from_deployment(owner, deployment, fee)?
+                    )?;
+                    let fee = vm.execute_fee_authorization(fee_authorization, Some(self.backend.query()), rng)?;
+                    (Transaction::from_execution(execution, Some(fee))?, response)
                 };
-                println!("Result of boroadcast deployment: {}", broadcast_transaction(transaction)?);
-                println!("✅ Created deployment transaction for '{}'", deployment_id.to_string());
-                Ok(Self { package })
+                println!("✅ Created execution transaction for '{}'", locator.to_string());
+                println!("Response from transaction broadcast: {}", self.backend.broadcast_transaction(transaction.clone())?.ensure_accepted()?);
+                ExecutionReport::decode_with_response(&transaction, account.view_key(), &response)
+            }
+            /// Computes the execution of a function of the program, without its fee, and returns it
+            /// with the VM that loaded the program, the latest block height and the plaintext
+            /// response of the function.
+            fn prepare_execution(
+                &self,
+                account: &Account<N>,
+                function_name: &str,
+                args: &[Value<N>],
+            ) -> Result<(VM<N, ConsensusMemory<N>>, Execution<N>, u32, Response<N>)> {
+                let rng = &mut rand::thread_rng();
+                let function_id = Identifier::<N>::from_str(function_name)?;
+                let store = ConsensusStore::<N, ConsensusMemory<N>>::open(0u16)?;
+                let vm = VM::from(store)?;
+                self.backend.load_program(&mut vm.process().write(), &self.program_id)?;
+                let query = self.backend.query();
+                let height = query.current_block_height()?;
+                let authorization = vm.authorize(account.private_key(), self.program_id, function_id, args.iter(), rng)?;
+                let response = vm.process().read().evaluate::<N::Circuit>(authorization.replicate())?;
+                let execution = vm
+                    .execute_authorization(authorization, None, Some(query), rng)?
+                    .execution()
+                    .cloned()
+                    .ok_or_else(|| anyhow!("The transaction does not contain an execution"))?;
+                Ok((vm, execution, height, response))
             }
             $(
+            #[allow(unused_parens)]
             pub fn $function_name(&self,
-                                  account: &Account<Nw>,
+                                  account: &Account<N>,
                                   $($input_name: $input_type),*) -> Result<($($output_type),*), Error> {
-                let program_name = stringify!($program_name).to_string();
-                let program_id = ProgramID::try_from("dev.aleo").unwrap();
-                let function_name = stringify!($function_name).to_string();
-                let function_id = Identifier::from_str(&function_name).unwrap();
-                let args: Vec<Value<Nw>> = vec![
-                    $(($input_name).to_value()),*
+                let function_name = leology::generate_bindings!(@aleo_name $function_name $($function_id)?);
+                let locator = Locator::<N>::new(self.program_id, Identifier::from_str(function_name)?);
+                let args: Vec<Value<N>> = vec![
+                    $(ToValue::<N>::to_value(&$input_name)),*
                 ];
-                let rng = &mut rand::thread_rng();
-                println!("Transaction of function {}:", function_name);
-                let query = "http://127.0.0.1:3030".to_string();
-                let private_key = account.private_key();
-                let priority_fee = 0;
-                //let locator = Locator::<Nw>::from_str(&format!("{}/{}", &program_name, &function_name))?;
-                let locator = Locator::<Nw>::new(ProgramID::try_from("dev.aleo")?, function_id);
-                let transaction: Transaction<Nw> = {
-                    let rng = &mut rand::thread_rng();
-                    let storage_mode = 0u16;
-                    let store = ConsensusStore::<Nw, ConsensusMemory<Nw>>::open(storage_mode)?;
-                    let vm = VM::from(store)?;
-                    load_program(&query, &mut vm.process().write(), &program_id)?;
-                    let fee_record = None;
-                    vm.execute(
-                        &private_key,
-                        (program_id, function_id),
-                        args.iter(),
-                        fee_record,
-                        priority_fee,
-                        Some(Query::from(&query)),
-                        rng,)?
-                };
-                let public_balance = get_public_balance(&account.address(), &query)?;
-                let storage_cost = transaction
-                    .execution()
-                    .ok_or_else(|| anyhow!("The transaction does not contain an execution"))?
-                    .size_in_bytes()?;
-                let base_fee = storage_cost.saturating_add(priority_fee);
-                if public_balance < base_fee {
-                    bail!(
-                        "❌ The public balance of {} is insufficient to pay the base fee for `{}`",
-                        public_balance,
-                        locator.to_string()
-                        );
-                }
-                println!("✅ Created execution transaction for '{}'", locator.to_string());
-                println!("Response from transaction broadcast: {}", broadcast_transaction(transaction.clone())?);
-                let execution = match transaction {
-                    Transaction::Execute(_, _, execution, _) => execution,
-                    _ => panic!("Not an execution."),
-                };
-
-                let mut transitions = execution.transitions();
-                let outputs_iter = transitions.next().unwrap().outputs().iter();
-                let outputs: Vec<Value<Nw>> = outputs_iter.filter_map(|output| {
-                    match output {
-                      Output::Constant(_, plaintext) | Output::Public(_, plaintext) => Some(Value::Plaintext(plaintext.clone().unwrap())),
-                      Output::Record(_, _, record_ciphertext) => Some(Value::Record(record_ciphertext.clone().unwrap().decrypt(account.view_key()).unwrap())),
-                      Output::Future(_, future) => Some(Value::Future(future.clone().unwrap())),
-                      _ => None,
-                    }
-                }).collect();
-                let mut outputs_iter = outputs.iter();
+                let report = self.execute_with_report(account, function_name, &args, &self.fee_strategy, self.priority_fee)?;
+                let mut outputs_iter = report.outputs(&locator)?.into_iter().enumerate();
 
-                Ok(($(
-                    <$output_type>::from_value(outputs_iter.next().unwrap().clone())
-                ),*))
+                Ok(($({
+                    let (index, output) = outputs_iter.next().ok_or_else(|| {
+                        anyhow!("'{}' returned fewer outputs than the bindings expect", locator)
+                    })?;
+                    <$output_type as TryFromValue<N>>::try_from_value(output).with_context(|| {
+                        format!("Failed to decode output {} of '{}'", index, locator)
+                    })?
+                }),*))
             }
             )*
         }
     };
 }
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use snarkvm::utilities::TestRng;
+    use std::fmt::Debug;
+
+    fn assert_round_trip<T: ToValue<Nw> + TryFromValue<Nw> + PartialEq + Debug>(expected: T) {
+        assert_eq!(T::try_from_value(expected.to_value()).unwrap(), expected);
+    }
+
+    #[test]
+    fn test_native_round_trip() {
+        assert_round_trip(true);
+        assert_round_trip(false);
+        assert_round_trip(i8::MIN);
+        assert_round_trip(i16::MIN);
+        assert_round_trip(i32::MIN);
+        assert_round_trip(i64::MIN);
+        assert_round_trip(i128::MIN);
+        assert_round_trip(u8::MAX);
+        assert_round_trip(u16::MAX);
+        assert_round_trip(u32::MAX);
+        assert_round_trip(u64::MAX);
+        assert_round_trip(u128::MAX);
+    }
+
+    #[test]
+    fn test_console_round_trip() {
+        let mut rng = TestRng::default();
+        let account = Account::<Nw>::new(&mut rng).unwrap();
+        let signature = account.sign_bytes(b"leology", &mut rng).unwrap();
+
+        assert_round_trip(account.address());
+        assert_round_trip(Boolean::<Nw>::new(true));
+        assert_round_trip(Field::<Nw>::rand(&mut rng));
+        assert_round_trip(Group::<Nw>::rand(&mut rng));
+        assert_round_trip(I8::<Nw>::rand(&mut rng));
+        assert_round_trip(I16::<Nw>::rand(&mut rng));
+        assert_round_trip(I32::<Nw>::rand(&mut rng));
+        assert_round_trip(I64::<Nw>::rand(&mut rng));
+        assert_round_trip(I128::<Nw>::rand(&mut rng));
+        assert_round_trip(U8::<Nw>::rand(&mut rng));
+        assert_round_trip(U16::<Nw>::rand(&mut rng));
+        assert_round_trip(U32::<Nw>::rand(&mut rng));
+        assert_round_trip(U64::<Nw>::rand(&mut rng));
+        assert_round_trip(U128::<Nw>::rand(&mut rng));
+        assert_round_trip(Scalar::<Nw>::rand(&mut rng));
+        assert_round_trip(signature);
+        assert_round_trip(StringType::<Nw>::new("leology"));
+        assert_round_trip(Literal::<Nw>::from_str("10u32").unwrap());
+    }
+
+    generate_struct! { Point { x: u64, y: i8 } }
+    generate_struct! { Path { points: [Point; 2], closed: bool } }
+    generate_struct! { Keywords { r#move: u64, r#ref: bool } }
+
+    #[test]
+    fn test_array_round_trip() {
+        assert_round_trip([1u8, 2, 3]);
+        assert_round_trip([[true, false], [false, true]]);
+        assert_eq!(
+            [1u32, 2].to_value(),
+            Value::<Nw>::from_str("[1u32, 2u32]").unwrap()
+        );
+    }
+
+    #[test]
+    fn test_struct_round_trip() {
+        let point = Point { x: 1, y: -1 };
+        assert_eq!(
+            point.to_value(),
+            Value::<Nw>::from_str("{ x: 1u64, y: -1i8 }").unwrap()
+        );
+        assert_round_trip(point.clone());
+        assert_round_trip(Path {
+            points: [point, Point { x: 2, y: 2 }],
+            closed: true,
+        });
+    }
+
+    #[test]
+    fn test_raw_identifier_round_trip() {
+        let keywords = Keywords {
+            r#move: 1,
+            r#ref: true,
+        };
+        assert_eq!(
+            keywords.to_value(),
+            Value::<Nw>::from_str("{ move: 1u64, ref: true }").unwrap()
+        );
+        assert_round_trip(keywords);
+    }
+
+    // Only checks that names clashing with the methods of the bindings can be bound.
+    #[allow(dead_code)]
+    mod renamed {
+        use crate::*;
+
+        generate_bindings! {
+            Renamed("renamed.aleo"), {
+                [
+                { deploy_function("deploy"), (r0: u64), (u64) },
+                ],
+                [{ TicketRecord("ticket"), (new_field("new"): bool) }],
+                [
+                { program_id_mapping("program_id"), Field<N> => u64 },
+                ],
+            }
+        }
+    }
+
+    #[test]
+    fn test_renamed_record_entry() {
+        let owner = Account::<Nw>::new(&mut TestRng::default())
+            .unwrap()
+            .address();
+        let record = Value::<Nw>::from_str(&format!(
+            "{{ owner: {owner}.private, new: true.private, _nonce: 0group.public }}"
+        ))
+        .unwrap();
+        let ticket = renamed::TicketRecord::try_from_value(record).unwrap();
+        assert!(ticket.new_field().unwrap());
+    }
+
+    #[test]
+    fn test_type_mismatch() {
+        let error = <u64 as TryFromValue<Nw>>::try_from_value(7u32.to_value()).unwrap_err();
+        assert_eq!(
+            error.downcast_ref::<LeologyError>(),
+            Some(&LeologyError::UnexpectedValue {
+                expected: "u64".to_string(),
+                found: "u32".to_string(),
+            })
+        );
+
+        let value = Value::<Nw>::from_str(
+            "{ points: [{ x: 1u64, y: 1i8 }, { x: 2u64, y: 2u8 }], closed: true }",
+        )
+        .unwrap();
+        let error = Path::try_from_value(value).unwrap_err();
+        let messages: Vec<String> = error.chain().map(ToString::to_string).collect();
+        assert_eq!(
+            messages,
+            [
+                "Failed to decode member 'points' of struct 'Path'",
+                "Failed to decode array element 1",
+                "Failed to decode member 'y' of struct 'Point'",
+                "Expected a value of type i8, found u8",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_record_mismatch() {
+        let owner = Account::<Nw>::new(&mut TestRng::default())
+            .unwrap()
+            .address();
+        let record = |entries: &str| {
+            Value::<Nw>::from_str(&format!(
+                "{{ owner: {owner}.private, {entries}, _nonce: 0group.public }}"
+            ))
+            .unwrap()
+        };
+        assert!(CreditsRecord::try_from_value(record("microcredits: 10u64.private")).is_ok());
+        let error = CreditsRecord::try_from_value(record("number: 10u64.private")).unwrap_err();
+        assert_eq!(
+            error.downcast_ref::<LeologyError>(),
+            Some(&LeologyError::UnexpectedValue {
+                expected: "record 'CreditsRecord' with entries (microcredits)".to_string(),
+                found: "record with entries (number)".to_string(),
+            })
+        );
+        assert!(CreditsRecord::try_from_value(record(
+            "microcredits: 10u64.private, number: 1u8.private"
+        ))
+        .is_err());
+    }
+
+    #[test]
+    fn test_native_value_matches_literal() {
+        assert_eq!(7u32.to_value(), Value::<Nw>::from_str("7u32").unwrap());
+        assert_eq!((-7i16).to_value(), Value::<Nw>::from_str("-7i16").unwrap());
+        assert_eq!(true.to_value(), Value::<Nw>::from_str("true").unwrap());
+    }
+    #[test]
+    fn test_record_serial_number() {
+        let rng = &mut TestRng::default();
+        let (alice, bob) = (Account::<Nw>::new(rng).unwrap(), Account::new(rng).unwrap());
+        let record = Record::<Nw, Plaintext<Nw>>::from_str(&format!(
+            "{{ owner: {}.private, number: 10u64.private, _nonce: 0group.public }}",
+            alice.address()
+        ))
+        .unwrap();
+        let program_id = ProgramID::from_str("dev.aleo").unwrap();
+        let rec = Identifier::from_str("rec").unwrap();
+        let commitment = record.to_commitment(&program_id, &rec).unwrap();
+        assert_eq!(
+            record_serial_number(&record, &program_id, &rec, &alice).unwrap(),
+            Record::<Nw, Plaintext<Nw>>::serial_number(*alice.private_key(), commitment).unwrap()
+        );
+        assert!(record_serial_number(&record, &program_id, &rec, &bob).is_err());
+    }
+    #[test]
+    fn test_mapping_value_url() {
+        let key = Plaintext::<Nw>::from_str("{ x: 1u64, y: [1i8, 2i8] }").unwrap();
+        let url = mapping_value_url(
+            &ProgramID::from_str("dev.aleo").unwrap(),
+            &Identifier::from_str("points").unwrap(),
+            &key,
+            &LeologyConfig::default(),
+        );
+        let (path, encoded_key) = url.rsplit_once('/').unwrap();
+        assert_eq!(
+            path,
+            "http://localhost:3030/testnet/program/dev.aleo/mapping/points"
+        );
+        assert!(!encoded_key.contains([' ', '{', '[', '/', '\n']));
+        assert_eq!(
+            percent_encoding::percent_decode_str(encoded_key)
+                .decode_utf8()
+                .unwrap(),
+            key.to_string()
+        );
+    }
+    /// Answers the next requests on a local port with the given statuses and bodies, in order.
+    pub(crate) fn serve(responses: Vec<(&'static str, String)>) -> LeologyConfig {
+        use std::io::{BufRead, BufReader, Write};
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let endpoint = format!("http://{}", listener.local_addr().unwrap());
+        std::thread::spawn(move || {
+            for (status, body) in responses {
+                let (mut stream, _) = listener.accept().unwrap();
+                // Read the request head, which ends with an empty line.
+                let mut lines = BufReader::new(&stream).lines();
+                while !lines.next().unwrap().unwrap().is_empty() {}
+                let response = format!(
+                    "HTTP/1.1 {status}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                    body.len()
+                );
+                stream.write_all(response.as_bytes()).unwrap();
+            }
+        });
+        LeologyConfig {
+            endpoint,
+            poll_interval: Duration::ZERO,
+            ..Default::default()
+        }
+    }
+    /// Answers the next request on a local port with the given status and body.
+    pub(crate) fn serve_once(status: &'static str, body: &str) -> LeologyConfig {
+        serve(vec![(status, body.to_string())])
+    }
+    #[test]
+    fn test_find_program_errors() {
+        let program_id = ProgramID::<Nw>::from_str("dev.aleo").unwrap();
+        let not_found = serve_once(
+            "500 Internal Server Error",
+            "Something went wrong: Missing program for ID dev.aleo",
+        );
+        assert!(find_program(&program_id, &not_found).unwrap().is_none());
+        assert!(find_program(&program_id, &serve_once("404 Not Found", ""))
+            .unwrap()
+            .is_none());
+
+        // Other failures do not mean that the program is missing.
+        let failure = serve_once("503 Service Unavailable", "The node is syncing");
+        let error = find_program(&program_id, &failure).unwrap_err();
+        assert!(error.to_string().contains("syncing"));
+    }
+    #[test]
+    fn test_contains_serial_number_errors() {
+        let serial_number = Field::<Nw>::from_u64(7);
+        let not_found = serve_once(
+            "500 Internal Server Error",
+            "Something went wrong: Failed to find the transition ID for the given input or output ID",
+        );
+        assert!(!contains_serial_number(&serial_number, &not_found).unwrap());
+        assert!(!contains_serial_number(&serial_number, &serve_once("404 Not Found", "")).unwrap());
+
+        // Other failures do not mean that the record is unspent.
+        let failure = serve_once("503 Service Unavailable", "The node is syncing");
+        let error = contains_serial_number(&serial_number, &failure).unwrap_err();
+        assert!(error.to_string().contains("syncing"));
+    }
+}
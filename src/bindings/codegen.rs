@@ -0,0 +1,541 @@
+//! Generates `generate_struct!` and `generate_bindings!` invocations from compiled Aleo programs.
+//!
+//! The generator is meant to be called from a test crate's `build.rs`:
+//!
+//! ```ignore
+//! fn main() {
+//!     leology::bindings::codegen::build_bindings(".", "bindings.rs").unwrap();
+//! }
+//! ```
+//!
+//! and the result pulled into the tests with
+//! `include!(concat!(env!("OUT_DIR"), "/bindings.rs"));`.
+
+use super::*;
+use std::collections::HashSet;
+use std::fmt::Write as _;
+use std::fs;
+use std::path::Path;
+
+/// The path of the compiled program, relative to the Leo package root.
+pub const COMPILED_PROGRAM_PATH: &str = "build/main.aleo";
+
+/// The Rust keywords that are valid Aleo identifiers, bound through raw identifiers.
+const RUST_KEYWORDS: &[&str] = &[
+    "abstract", "as", "async", "await", "become", "box", "break", "const", "continue", "do", "dyn",
+    "else", "enum", "extern", "false", "final", "fn", "for", "gen", "if", "impl", "in", "let",
+    "loop", "macro", "match", "mod", "move", "mut", "override", "priv", "pub", "ref", "return",
+    "static", "struct", "trait", "true", "try", "type", "typeof", "unsafe", "unsized", "use",
+    "virtual", "where", "while", "yield",
+];
+
+/// The Rust keywords that cannot be raw identifiers.
+const RESERVED_KEYWORDS: &[&str] = &["crate", "self", "Self", "super"];
+
+/// The types the generated bindings refer to through the glob import of `leology::bindings`
+/// and the prelude, which a generated struct of the same name would shadow.
+const RESERVED_TYPE_NAMES: &[&str] = &[
+    "Account",
+    "Address",
+    "AleoNetwork",
+    "Arc",
+    "AsRef",
+    "Backend",
+    "Box",
+    "Clone",
+    "ConsensusMemory",
+    "ConsensusStore",
+    "Context",
+    "Debug",
+    "Default",
+    "Error",
+    "Execution",
+    "ExecutionReport",
+    "FeeEstimate",
+    "FeeStrategy",
+    "Field",
+    "Future",
+    "Group",
+    "Identifier",
+    "IndexMap",
+    "Locator",
+    "Mutex",
+    "N",
+    "Network",
+    "Nw",
+    "Option",
+    "Package",
+    "PartialEq",
+    "Path",
+    "Plaintext",
+    "Program",
+    "ProgramID",
+    "ProgramRecord",
+    "Record",
+    "Response",
+    "Result",
+    "Scalar",
+    "Signature",
+    "String",
+    "StringType",
+    "ToPlaintext",
+    "ToValue",
+    "Transaction",
+    "TryFromValue",
+    "TryInto",
+    "VM",
+    "Value",
+    "Vec",
+    "Wallet",
+];
+
+/// The associated items of the program struct, which a function or mapping of the same name
+/// would clash with.
+const RESERVED_METHOD_NAMES: &[&str] = &[
+    "PROGRAM_ID",
+    "attach",
+    "bind",
+    "deploy",
+    "deploy_or_attach",
+    "ensure_program_id",
+    "estimate_fee",
+    "execute_with_report",
+    "fee_wallet",
+    "from_directory",
+    "prepare_execution",
+    "program_id",
+    "with_fee_strategy",
+    "with_priority_fee",
+];
+
+/// The associated functions of the record structs, which a record entry of the same name would
+/// clash with.
+const RESERVED_FIELD_NAMES: &[&str] = &["new"];
+
+/// Reads the compiled program of the Leo package at `package_dir`, and writes its bindings
+/// into `$OUT_DIR/{file_name}`. Intended to be called from a build script.
+pub fn build_bindings(package_dir: impl AsRef<Path>, file_name: &str) -> Result<()> {
+    let program_path = package_dir.as_ref().join(COMPILED_PROGRAM_PATH);
+    println!("cargo:rerun-if-changed={}", program_path.display());
+
+    let out_dir = std::env::var("OUT_DIR").map_err(|_| anyhow!("OUT_DIR is not set"))?;
+    let bindings = generate_bindings_from_file(&program_path)?;
+    fs::write(Path::new(&out_dir).join(file_name), bindings)?;
+    Ok(())
+}
+
+/// Generates the bindings for the compiled program at `program_path`.
+pub fn generate_bindings_from_file(program_path: impl AsRef<Path>) -> Result<String> {
+    let program_path = program_path.as_ref();
+    let source = fs::read_to_string(program_path)
+        .map_err(|e| anyhow!("Failed to read '{}' - {e}", program_path.display()))?;
+    let program = Program::<Nw>::from_str(&source)?;
+    generate_bindings(&program)
+}
+
+/// The Rust names of the types declared by a program.
+struct TypeNames {
+    /// The wrapper structs of the records.
+    records: IndexMap<Identifier<Nw>, String>,
+    /// The structs mirroring the Aleo structs.
+    structs: IndexMap<Identifier<Nw>, String>,
+    /// The structs that take the network as a type parameter, as they hold network-specific values.
+    generic_structs: HashSet<Identifier<Nw>>,
+}
+
+/// Generates a `generate_bindings!` invocation covering every function, record and mapping of `program`.
+pub fn generate_bindings(program: &Program<Nw>) -> Result<String> {
+    let program_name = to_camel_case(&program.id().name().to_string());
+    let mut names = TypeNames {
+        records: program
+            .records()
+            .keys()
+            .map(|name| (*name, type_name(&program_name, name, "Record")))
+            .collect(),
+        structs: program
+            .structs()
+            .keys()
+            .map(|name| (*name, type_name(&program_name, name, "Struct")))
+            .collect(),
+        generic_structs: HashSet::new(),
+    };
+    // Structs are declared before they are used, so their members only refer to earlier structs.
+    for (name, struct_type) in program.structs() {
+        let is_generic = struct_type
+            .members()
+            .values()
+            .any(|plaintext_type| is_network_specific(plaintext_type, &names.generic_structs));
+        if is_generic {
+            names.generic_structs.insert(*name);
+        }
+    }
+
+    let mut functions = String::new();
+    for function in program.functions().values() {
+        let inputs = function
+            .input_types()
+            .iter()
+            .enumerate()
+            .map(|(index, value_type)| {
+                Ok(format!(
+                    "r{index}: {}",
+                    value_type_name(value_type, &names)?
+                ))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let outputs = function
+            .output_types()
+            .iter()
+            .map(|value_type| value_type_name(value_type, &names))
+            .collect::<Result<Vec<_>>>()?;
+        writeln!(
+            functions,
+            "        {{ {}, ({}), ({}) }},",
+            method_name(function.name(), RESERVED_METHOD_NAMES, "function")?,
+            inputs.join(", "),
+            outputs.join(", ")
+        )?;
+    }
+
+    let mut records = Vec::new();
+    for (name, record_type) in program.records() {
+        let fields = record_type
+            .entries()
+            .iter()
+            .map(|(field, entry_type)| {
+                Ok(format!(
+                    "{}: {}",
+                    method_name(field, RESERVED_FIELD_NAMES, "field")?,
+                    plaintext_type_name(entry_type.plaintext_type(), &names)?
+                ))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        records.push(format!(
+            "{}(\"{name}\"), ({})",
+            names.records[name],
+            fields.join(", ")
+        ));
+    }
+
+    let mut structs = String::new();
+    for (name, struct_type) in program.structs() {
+        let members = struct_type
+            .members()
+            .iter()
+            .map(|(member, plaintext_type)| {
+                Ok(format!(
+                    "{}: {}",
+                    rust_identifier(member)?,
+                    plaintext_type_name(plaintext_type, &names)?
+                ))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        writeln!(
+            structs,
+            "generate_struct! {{ {} {{ {} }} }}",
+            struct_type_name(name, &names),
+            members.join(", ")
+        )?;
+    }
+
+    let mut mappings = String::new();
+    for (name, mapping) in program.mappings() {
+        writeln!(
+            mappings,
+            "        {{ {}, {} => {} }},",
+            method_name(name, RESERVED_METHOD_NAMES, "mapping")?,
+            plaintext_type_name(mapping.key().plaintext_type(), &names)?,
+            plaintext_type_name(mapping.value().plaintext_type(), &names)?
+        )?;
+    }
+
+    let mut bindings = String::new();
+    writeln!(
+        bindings,
+        "// This file is generated by leology from `{}`. Do not edit.",
+        program.id()
+    )?;
+    writeln!(bindings)?;
+    if !structs.is_empty() {
+        writeln!(bindings, "{structs}")?;
+    }
+    writeln!(bindings, "generate_bindings! {{")?;
+    writeln!(bindings, "    {program_name}(\"{}\"), {{", program.id())?;
+    writeln!(bindings, "        [")?;
+    write!(bindings, "{functions}")?;
+    writeln!(bindings, "        ],")?;
+    writeln!(bindings, "        [{{ {} }}],", records.join("; "))?;
+    writeln!(bindings, "        [")?;
+    write!(bindings, "{mappings}")?;
+    writeln!(bindings, "        ],")?;
+    writeln!(bindings, "    }}")?;
+    writeln!(bindings, "}}")?;
+    Ok(bindings)
+}
+
+/// Returns the Rust type used to bind the given value type.
+///
+/// The types of network-specific values are generic over the network `N` of the program struct.
+fn value_type_name(value_type: &ValueType<Nw>, names: &TypeNames) -> Result<String> {
+    match value_type {
+        ValueType::Constant(plaintext_type)
+        | ValueType::Public(plaintext_type)
+        | ValueType::Private(plaintext_type) => plaintext_type_name(plaintext_type, names),
+        ValueType::Record(name) => names
+            .records
+            .get(name)
+            .map(|record_name| format!("{record_name}<N>"))
+            .ok_or_else(|| anyhow!("Record '{name}' is not defined in the program")),
+        ValueType::ExternalRecord(_) => Ok("Record<N, Plaintext<N>>".to_string()),
+        ValueType::Future(_) => Ok("Future<N>".to_string()),
+    }
+}
+
+/// Returns the Rust type used to bind the given plaintext type.
+fn plaintext_type_name(plaintext_type: &PlaintextType<Nw>, names: &TypeNames) -> Result<String> {
+    match plaintext_type {
+        PlaintextType::Literal(literal_type) => Ok(literal_type_name(literal_type).to_string()),
+        PlaintextType::Struct(name) => Ok(struct_type_name(name, names)),
+        PlaintextType::Array(array_type) => Ok(format!(
+            "[{}; {}]",
+            plaintext_type_name(array_type.next_element_type(), names)?,
+            **array_type.length()
+        )),
+    }
+}
+
+/// Returns the Rust type of a struct, which takes the network as a parameter if it holds
+/// network-specific values.
+fn struct_type_name(name: &Identifier<Nw>, names: &TypeNames) -> String {
+    let type_name = &names.structs[name];
+    match names.generic_structs.contains(name) {
+        true => format!("{type_name}<N>"),
+        false => type_name.clone(),
+    }
+}
+
+/// Returns `true` if the Rust type binding the plaintext type depends on the network.
+fn is_network_specific(
+    plaintext_type: &PlaintextType<Nw>,
+    generic_structs: &HashSet<Identifier<Nw>>,
+) -> bool {
+    match plaintext_type {
+        PlaintextType::Literal(literal_type) => literal_type_name(literal_type).ends_with("<N>"),
+        PlaintextType::Struct(name) => generic_structs.contains(name),
+        PlaintextType::Array(array_type) => {
+            is_network_specific(array_type.next_element_type(), generic_structs)
+        }
+    }
+}
+
+/// Returns the Rust type used to bind the given literal type.
+fn literal_type_name(literal_type: &LiteralType) -> &'static str {
+    match literal_type {
+        LiteralType::Address => "Address<N>",
+        LiteralType::Boolean => "bool",
+        LiteralType::Field => "Field<N>",
+        LiteralType::Group => "Group<N>",
+        LiteralType::I8 => "i8",
+        LiteralType::I16 => "i16",
+        LiteralType::I32 => "i32",
+        LiteralType::I64 => "i64",
+        LiteralType::I128 => "i128",
+        LiteralType::U8 => "u8",
+        LiteralType::U16 => "u16",
+        LiteralType::U32 => "u32",
+        LiteralType::U64 => "u64",
+        LiteralType::U128 => "u128",
+        LiteralType::Scalar => "Scalar<N>",
+        LiteralType::Signature => "Signature<N>",
+        LiteralType::String => "StringType<N>",
+    }
+}
+
+/// Returns the Rust name of a record or struct, adding the suffix when the name would clash with
+/// the program struct or shadow a type the bindings use.
+fn type_name(program_name: &str, name: &Identifier<Nw>, suffix: &str) -> String {
+    let name = to_camel_case(&name.to_string());
+    match name == program_name || RESERVED_TYPE_NAMES.contains(&name.as_str()) {
+        true => format!("{name}{suffix}"),
+        false => name,
+    }
+}
+
+/// Returns the Rust identifier of a function, mapping, record entry or struct member, which is a
+/// raw identifier if the Aleo name is a Rust keyword.
+fn rust_identifier(name: &Identifier<Nw>) -> Result<String> {
+    let name = name.to_string();
+    ensure!(
+        !RESERVED_KEYWORDS.contains(&name.as_str()),
+        "'{name}' is a Rust keyword that cannot be bound"
+    );
+    match RUST_KEYWORDS.contains(&name.as_str()) {
+        true => Ok(format!("r#{name}")),
+        false => Ok(name),
+    }
+}
+
+/// Returns the binding of a function, mapping or record entry. When the name would clash with
+/// one of the `reserved` items, the suffix is added and the Aleo name is passed along.
+fn method_name(name: &Identifier<Nw>, reserved: &[&str], suffix: &str) -> Result<String> {
+    match reserved.contains(&name.to_string().as_str()) {
+        true => Ok(format!("{name}_{suffix}(\"{name}\")")),
+        false => rust_identifier(name),
+    }
+}
+
+/// Converts a `snake_case` Aleo identifier into a `CamelCase` Rust type name.
+fn to_camel_case(name: &str) -> String {
+    name.split('_')
+        .filter(|part| !part.is_empty())
+        .map(|part| {
+            let mut chars = part.chars();
+            match chars.next() {
+                Some(first) => first.to_uppercase().chain(chars).collect::<String>(),
+                None => String::new(),
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TOKEN: &str = r"
+program token.aleo;
+
+record token:
+    owner as address.private;
+    amount as u64.private;
+
+mapping account:
+    key as address.public;
+    value as u64.public;
+
+function mint_private:
+    input r0 as address.private;
+    input r1 as u64.private;
+    cast r0 r1 into r2 as token.record;
+    output r2 as token.record;
+
+function transfer_private:
+    input r0 as token.record;
+    input r1 as address.private;
+    input r2 as u64.private;
+    sub r0.amount r2 into r3;
+    cast r0.owner r3 into r4 as token.record;
+    cast r1 r2 into r5 as token.record;
+    output r4 as token.record;
+    output r5 as token.record;
+";
+
+    #[test]
+    fn test_generate_bindings() {
+        let program = Program::<Nw>::from_str(TOKEN).unwrap();
+        let bindings = generate_bindings(&program).unwrap();
+        assert!(bindings.contains("    Token(\"token.aleo\"), {"));
+        assert!(bindings.contains("{ mint_private, (r0: Address<N>, r1: u64), (TokenRecord<N>) },"));
+        assert!(bindings.contains(
+            "{ transfer_private, (r0: TokenRecord<N>, r1: Address<N>, r2: u64), (TokenRecord<N>, TokenRecord<N>) },"
+        ));
+        assert!(bindings.contains("[{ TokenRecord(\"token\"), (amount: u64) }],"));
+        assert!(bindings.contains("{ account, Address<N> => u64 },"));
+    }
+
+    #[test]
+    fn test_generate_struct_bindings() {
+        let program = Program::<Nw>::from_str(
+            r"
+program shapes.aleo;
+
+struct point:
+    x as u64;
+    y as u64;
+
+struct polygon:
+    corners as [point; 3u32];
+    grid as [[boolean; 2u32]; 2u32];
+
+struct owned:
+    creator as address;
+    shape as polygon;
+
+struct layer:
+    shapes as [owned; 2u32];
+
+mapping polygons:
+    key as field.public;
+    value as polygon.public;
+
+function centroid:
+    input r0 as polygon.private;
+    input r1 as point.private;
+    output r1 as point.private;
+",
+        )
+        .unwrap();
+        let bindings = generate_bindings(&program).unwrap();
+        assert!(bindings.contains("generate_struct! { Point { x: u64, y: u64 } }"));
+        assert!(bindings.contains(
+            "generate_struct! { Polygon { corners: [Point; 3], grid: [[bool; 2]; 2] } }"
+        ));
+        assert!(bindings.contains("{ centroid, (r0: Polygon, r1: Point), (Point) },"));
+        assert!(bindings.contains("{ polygons, Field<N> => Polygon },"));
+        assert!(bindings
+            .contains("generate_struct! { Owned<N> { creator: Address<N>, shape: Polygon } }"));
+        assert!(bindings.contains("generate_struct! { Layer<N> { shapes: [Owned<N>; 2] } }"));
+    }
+
+    #[test]
+    fn test_generate_keyword_bindings() {
+        let program = Program::<Nw>::from_str(
+            r"
+program keywords.aleo;
+
+struct plaintext:
+    move as u64;
+    ref as boolean;
+
+record account:
+    owner as address.private;
+    mut as u64.private;
+    new as boolean.private;
+
+mapping use:
+    key as field.public;
+    value as plaintext.public;
+
+mapping program_id:
+    key as field.public;
+    value as u64.public;
+
+function loop:
+    input r0 as plaintext.private;
+    output r0 as plaintext.private;
+
+function deploy:
+    input r0 as u64.private;
+    output r0 as u64.private;
+",
+        )
+        .unwrap();
+        let bindings = generate_bindings(&program).unwrap();
+        assert!(
+            bindings.contains("generate_struct! { PlaintextStruct { r#move: u64, r#ref: bool } }")
+        );
+        assert!(bindings
+            .contains("[{ AccountRecord(\"account\"), (r#mut: u64, new_field(\"new\"): bool) }],"));
+        assert!(bindings.contains("{ r#use, Field<N> => PlaintextStruct },"));
+        assert!(bindings.contains("{ r#loop, (r0: PlaintextStruct), (PlaintextStruct) },"));
+        // Names clashing with the methods of the bindings are suffixed.
+        assert!(bindings.contains("{ program_id_mapping(\"program_id\"), Field<N> => u64 },"));
+        assert!(bindings.contains("{ deploy_function(\"deploy\"), (r0: u64), (u64) },"));
+    }
+
+    #[test]
+    fn test_to_camel_case() {
+        assert_eq!(to_camel_case("dev"), "Dev");
+        assert_eq!(to_camel_case("my_token_v2"), "MyTokenV2");
+    }
+}
@@ -0,0 +1,210 @@
+use crate::bindings::*;
+use crate::*;
+use serde::Deserialize;
+use std::fs;
+
+/// The configuration file read by [`LeologyConfig::load`].
+pub const CONFIG_FILE: &str = "leology.toml";
+
+/// Where the devnet is reachable, and how long to wait for it.
+///
+/// [`LeologyConfig::load`] starts from the defaults and overrides them, in order, with the
+/// settings of `leology.toml`, of `.env` and of the environment variables:
+///
+/// | `leology.toml`              | `.env` and environment              | Default                 |
+/// |-----------------------------|-------------------------------------|-------------------------|
+/// | `endpoint`                  | `LEOLOGY_ENDPOINT`                  | [`DEFAULT_ENDPOINT`]    |
+/// | `network`                   | `LEOLOGY_NETWORK`                   | `testnet`               |
+/// | `confirmation_timeout_secs` | `LEOLOGY_CONFIRMATION_TIMEOUT_SECS` | `120`                   |
+/// | `poll_interval_ms`          | `LEOLOGY_POLL_INTERVAL_MS`          | `1000`                  |
+/// | `ready_timeout_secs`        | `LEOLOGY_READY_TIMEOUT_SECS`        | `120`                   |
+///
+/// The variables are prefixed so that the `NETWORK` and `ENDPOINT` read by the Leo CLI, often
+/// from the same `.env`, do not configure the devnet.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LeologyConfig {
+    /// The base URL of the node's REST API.
+    pub endpoint: String,
+    /// The network segment of the REST API paths, such as `testnet`.
+    pub network: String,
+    /// The maximum time to wait for a broadcast transaction to be included in a block.
+    pub confirmation_timeout: Duration,
+    /// The time between two queries to the node while waiting.
+    pub poll_interval: Duration,
+    /// The maximum time to wait for the devnet to produce blocks.
+    pub ready_timeout: Duration,
+}
+
+impl Default for LeologyConfig {
+    fn default() -> Self {
+        Self {
+            endpoint: DEFAULT_ENDPOINT.to_string(),
+            network: "testnet".to_string(),
+            confirmation_timeout: Duration::from_secs(120),
+            poll_interval: Duration::from_secs(1),
+            ready_timeout: Duration::from_secs(120),
+        }
+    }
+}
+
+impl LeologyConfig {
+    /// Loads the configuration from the current directory and the environment.
+    pub fn load() -> Result<Self> {
+        Self::load_from(".")
+    }
+
+    /// Loads the configuration from `leology.toml` and `.env` in the given directory, and from
+    /// the environment.
+    pub fn load_from(directory: impl AsRef<Path>) -> Result<Self> {
+        Self::load_with_env(directory.as_ref(), std::env::vars())
+    }
+
+    /// Loads the configuration from the given directory, and from the given environment.
+    fn load_with_env(
+        directory: &Path,
+        env: impl IntoIterator<Item = (String, String)>,
+    ) -> Result<Self> {
+        let mut config = Self::default();
+
+        let config_file = directory.join(CONFIG_FILE);
+        if config_file.is_file() {
+            let settings = toml::from_str(&fs::read_to_string(&config_file)?)
+                .with_context(|| format!("Failed to parse '{}'", config_file.display()))?;
+            config.apply(settings);
+        }
+
+        let env_file = directory.join(".env");
+        if env_file.is_file() {
+            let vars = dotenvy::from_path_iter(&env_file)?
+                .collect::<Result<Vec<_>, _>>()
+                .with_context(|| format!("Failed to parse '{}'", env_file.display()))?;
+            config.apply(Settings::from_vars(vars)?);
+        }
+
+        config.apply(Settings::from_vars(env)?);
+        Ok(config)
+    }
+
+    /// Returns the URL of the REST API route at `path`, such as `block/height/latest`.
+    pub fn url(&self, path: impl std::fmt::Display) -> String {
+        format!(
+            "{}/{}/{path}",
+            self.endpoint.trim_end_matches('/'),
+            self.network
+        )
+    }
+
+    /// Returns how long to wait for broadcast transactions to be included in a block.
+    pub fn confirmation_policy(&self) -> ConfirmationPolicy {
+        ConfirmationPolicy {
+            timeout: self.confirmation_timeout,
+            interval: self.poll_interval,
+        }
+    }
+
+    /// Overrides the configuration with the given settings.
+    fn apply(&mut self, settings: Settings) {
+        if let Some(endpoint) = settings.endpoint {
+            self.endpoint = endpoint;
+        }
+        if let Some(network) = settings.network {
+            self.network = network;
+        }
+        if let Some(secs) = settings.confirmation_timeout_secs {
+            self.confirmation_timeout = Duration::from_secs(secs);
+        }
+        if let Some(ms) = settings.poll_interval_ms {
+            self.poll_interval = Duration::from_millis(ms);
+        }
+        if let Some(secs) = settings.ready_timeout_secs {
+            self.ready_timeout = Duration::from_secs(secs);
+        }
+    }
+}
+
+/// The prefix of the environment variables read by [`LeologyConfig::load`].
+pub const ENV_PREFIX: &str = "LEOLOGY_";
+
+/// The settings of one configuration source, each of which may be missing.
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Settings {
+    endpoint: Option<String>,
+    network: Option<String>,
+    confirmation_timeout_secs: Option<u64>,
+    poll_interval_ms: Option<u64>,
+    ready_timeout_secs: Option<u64>,
+}
+
+impl Settings {
+    /// Reads the settings from environment variables, ignoring unrelated ones.
+    fn from_vars(vars: impl IntoIterator<Item = (String, String)>) -> Result<Self> {
+        let parse = |key: &str, value: &str| -> Result<u64> {
+            value
+                .parse()
+                .map_err(|e| anyhow!("Invalid value '{value}' for {key} - {e}"))
+        };
+        let mut settings = Self::default();
+        for (key, value) in vars {
+            let Some(name) = key.strip_prefix(ENV_PREFIX) else {
+                continue;
+            };
+            match name {
+                "ENDPOINT" => settings.endpoint = Some(value),
+                "NETWORK" => settings.network = Some(value),
+                "CONFIRMATION_TIMEOUT_SECS" => {
+                    settings.confirmation_timeout_secs = Some(parse(&key, &value)?)
+                }
+                "POLL_INTERVAL_MS" => settings.poll_interval_ms = Some(parse(&key, &value)?),
+                "READY_TIMEOUT_SECS" => settings.ready_timeout_secs = Some(parse(&key, &value)?),
+                _ => {}
+            }
+        }
+        Ok(settings)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_load_precedence() {
+        let directory = std::env::temp_dir().join(format!("leology-config-{}", std::process::id()));
+        fs::create_dir_all(&directory).unwrap();
+        fs::write(
+            directory.join(CONFIG_FILE),
+            "endpoint = \"http://node:3030\"\nnetwork = \"canary\"\npoll_interval_ms = 250\n",
+        )
+        .unwrap();
+        fs::write(
+            directory.join(".env"),
+            "NETWORK=mainnet\nLEOLOGY_NETWORK=testnet\nPRIVATE_KEY=unrelated\nLEOLOGY_READY_TIMEOUT_SECS=5\n",
+        )
+        .unwrap();
+
+        let env = [
+            ("LEOLOGY_READY_TIMEOUT_SECS".to_string(), "7".to_string()),
+            ("ENDPOINT".to_string(), "http://leo:3030".to_string()),
+        ];
+        let config = LeologyConfig::load_with_env(&directory, env).unwrap();
+        assert_eq!(
+            config,
+            LeologyConfig {
+                endpoint: "http://node:3030".to_string(),
+                network: "testnet".to_string(),
+                poll_interval: Duration::from_millis(250),
+                ready_timeout: Duration::from_secs(7),
+                ..Default::default()
+            }
+        );
+        assert_eq!(
+            config.url("block/height/latest"),
+            "http://node:3030/testnet/block/height/latest"
+        );
+
+        let env = [("LEOLOGY_POLL_INTERVAL_MS".to_string(), "soon".to_string())];
+        assert!(LeologyConfig::load_with_env(&directory, env).is_err());
+        fs::remove_dir_all(&directory).unwrap();
+    }
+}
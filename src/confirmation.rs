@@ -0,0 +1,248 @@
+use crate::bindings::*;
+use crate::*;
+use std::fmt;
+use std::time::Instant;
+
+/// How long to wait for a broadcast transaction to be included in a block, and how often to check.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ConfirmationPolicy {
+    /// The maximum time to wait for the transaction to be included in a block.
+    pub timeout: Duration,
+    /// The time between two queries to the node.
+    pub interval: Duration,
+}
+
+impl Default for ConfirmationPolicy {
+    fn default() -> Self {
+        Self {
+            timeout: Duration::from_secs(120),
+            interval: Duration::from_secs(1),
+        }
+    }
+}
+
+/// What happened to a transaction once it was included in a block.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TransactionStatus {
+    /// The transaction and its finalize logic were applied.
+    Accepted,
+    /// The finalize logic failed; only the fee was charged.
+    Rejected,
+    /// The transaction was dropped from the block without charging a fee.
+    Aborted,
+}
+
+impl fmt::Display for TransactionStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Accepted => write!(f, "accepted"),
+            Self::Rejected => write!(f, "rejected"),
+            Self::Aborted => write!(f, "aborted"),
+        }
+    }
+}
+
+/// A broadcast transaction, as included in a block.
+#[derive(Clone, Debug)]
+pub struct Confirmation<N: Network> {
+    /// The ID of the broadcast transaction.
+    pub transaction_id: <N as Network>::TransactionID,
+    /// The height of the block that includes the transaction.
+    pub block_height: u32,
+    /// Whether the finalize logic was applied.
+    pub status: TransactionStatus,
+    /// The confirmed transaction, unless it was aborted.
+    pub confirmed_transaction: Option<ConfirmedTransaction<N>>,
+}
+
+impl<N: Network> Confirmation<N> {
+    /// Looks up the outcome of the given transaction in the block that includes it.
+    pub fn from_block(
+        block: &Block<N>,
+        transaction_id: &<N as Network>::TransactionID,
+    ) -> Result<Self> {
+        let confirmed_transaction = block
+            .transactions()
+            .find_confirmed_transaction_for_unconfirmed_transaction_id(transaction_id)
+            .cloned();
+        let status = match &confirmed_transaction {
+            Some(confirmed) if confirmed.is_accepted() => TransactionStatus::Accepted,
+            Some(_) => TransactionStatus::Rejected,
+            None if block.aborted_transaction_ids().contains(transaction_id) => {
+                TransactionStatus::Aborted
+            }
+            None => bail!(
+                "Block {} does not include transaction {transaction_id}",
+                block.height()
+            ),
+        };
+        Ok(Self {
+            transaction_id: *transaction_id,
+            block_height: block.height(),
+            status,
+            confirmed_transaction,
+        })
+    }
+
+    /// Returns `true` if the transaction and its finalize logic were applied.
+    pub fn is_accepted(&self) -> bool {
+        self.status == TransactionStatus::Accepted
+    }
+
+    /// Returns the confirmation if the transaction was accepted, or the reason it was not.
+    pub fn ensure_accepted(self) -> Result<Self, LeologyError> {
+        match self.status {
+            TransactionStatus::Accepted => Ok(self),
+            TransactionStatus::Rejected => Err(LeologyError::FinalizeRejected {
+                transaction_id: self.transaction_id.to_string(),
+                reason: self.rejection_reason(),
+            }),
+            TransactionStatus::Aborted => Err(LeologyError::Aborted {
+                transaction_id: self.transaction_id.to_string(),
+            }),
+        }
+    }
+
+    /// Describes the deployment or execution whose finalize logic failed.
+    fn rejection_reason(&self) -> String {
+        let rejected = self
+            .confirmed_transaction
+            .as_ref()
+            .and_then(ConfirmedTransaction::to_rejected);
+        match rejected {
+            Some(Rejected::Deployment(_, deployment)) => format!(
+                "the deployment of '{}' failed to finalize",
+                deployment.program_id()
+            ),
+            Some(Rejected::Execution(execution)) => match execution.peek() {
+                Ok(transition) => format!(
+                    "the finalize logic of '{}/{}' failed",
+                    transition.program_id(),
+                    transition.function_name()
+                ),
+                Err(_) => "the execution failed to finalize".to_string(),
+            },
+            None => "the transaction failed to finalize".to_string(),
+        }
+    }
+}
+
+impl<N: Network> fmt::Display for Confirmation<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} {} at block {}",
+            self.transaction_id, self.status, self.block_height
+        )
+    }
+}
+
+/// Polls the configured endpoint until the transaction is included in a block.
+pub fn wait_for_confirmation<N: Network>(
+    transaction_id: &<N as Network>::TransactionID,
+    config: &LeologyConfig,
+) -> Result<Confirmation<N>> {
+    let policy = config.confirmation_policy();
+    let start = Instant::now();
+    loop {
+        if let Some(block_hash) = find_block_hash::<N>(transaction_id, config)? {
+            let block = fetch_block(&block_hash, config)?;
+            return Confirmation::from_block(&block, transaction_id);
+        }
+        if start.elapsed() >= policy.timeout {
+            bail!(
+                "❌ Transaction {transaction_id} was not included in a block within {:?}",
+                policy.timeout
+            );
+        }
+        sleep(policy.interval);
+    }
+}
+
+/// Fetch the hash of the block including the transaction, if any, from the configured endpoint.
+fn find_block_hash<N: Network>(
+    transaction_id: &<N as Network>::TransactionID,
+    config: &LeologyConfig,
+) -> Result<Option<<N as Network>::BlockHash>> {
+    // Send a request to the query node.
+    let response = ureq::get(&config.url(format_args!("find/blockHash/{transaction_id}"))).call();
+
+    // The node answers with `null` until the transaction is known.
+    match response {
+        Ok(response) => response.into_json().map_err(|err| err.into()),
+        Err(ureq::Error::Status(404, _)) => Ok(None),
+        Err(ureq::Error::Status(_status, response)) => {
+            bail!(response
+                .into_string()
+                .unwrap_or("Response too large!".to_owned()))
+        }
+        Err(err) => bail!(err),
+    }
+}
+
+/// Fetch the block with the given hash from the configured endpoint.
+fn fetch_block<N: Network>(
+    block_hash: &<N as Network>::BlockHash,
+    config: &LeologyConfig,
+) -> Result<Block<N>> {
+    // Send a request to the query node.
+    let response = ureq::get(&config.url(format_args!("block/{block_hash}"))).call();
+
+    // Deserialize the block.
+    match response {
+        Ok(response) => response.into_json().map_err(|err| err.into()),
+        Err(err) => match err {
+            ureq::Error::Status(_status, response) => {
+                bail!(response
+                    .into_string()
+                    .unwrap_or("Response too large!".to_owned()))
+            }
+            err => bail!(err),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bindings::tests::{serve, serve_once};
+
+    /// Returns the genesis block of the default network, which includes a few transactions.
+    fn genesis_block() -> Block<Nw> {
+        Block::from_bytes_le(Nw::genesis_bytes()).unwrap()
+    }
+
+    #[test]
+    fn test_wait_for_confirmation() {
+        let block = genesis_block();
+        let transaction_id = block.transaction_ids().next().copied().unwrap();
+        let config = serve(vec![
+            ("200 OK", "null".to_string()),
+            ("200 OK", format!("\"{}\"", block.hash())),
+            ("200 OK", serde_json::to_string(&block).unwrap()),
+        ]);
+        let confirmation = wait_for_confirmation::<Nw>(&transaction_id, &config).unwrap();
+        assert_eq!(confirmation.block_height, 0);
+        assert!(confirmation.is_accepted());
+    }
+
+    #[test]
+    fn test_wait_for_confirmation_errors() {
+        let transaction_id = *genesis_block().transaction_ids().next().unwrap();
+
+        let mut config = serve_once("200 OK", "null");
+        config.confirmation_timeout = Duration::ZERO;
+        let error = wait_for_confirmation::<Nw>(&transaction_id, &config).unwrap_err();
+        assert!(error.to_string().contains("was not included in a block"));
+
+        // Failures other than a missing transaction are reported rather than retried.
+        let config = serve_once("500 Internal Server Error", "Something went wrong: storage");
+        let error = wait_for_confirmation::<Nw>(&transaction_id, &config).unwrap_err();
+        assert!(error.to_string().contains("storage"));
+        assert!(
+            find_block_hash::<Nw>(&transaction_id, &serve_once("404 Not Found", ""))
+                .unwrap()
+                .is_none()
+        );
+    }
+}
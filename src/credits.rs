@@ -0,0 +1,35 @@
+//! Bindings of `credits.aleo`, which every network deploys in its genesis block.
+
+use crate::*;
+
+generate_struct! { BondState<N> { validator: Address<N>, microcredits: u64 } }
+
+generate_bindings! {
+    Credits("credits.aleo"), {
+        [
+        { transfer_public, (receiver: Address<N>, microcredits: u64), (Future<N>) },
+        { transfer_private, (input: CreditsRecord<N>, receiver: Address<N>, microcredits: u64), (CreditsRecord<N>, CreditsRecord<N>) },
+        { transfer_public_to_private, (receiver: Address<N>, microcredits: u64), (CreditsRecord<N>, Future<N>) },
+        { transfer_private_to_public, (input: CreditsRecord<N>, receiver: Address<N>, microcredits: u64), (CreditsRecord<N>, Future<N>) },
+        { join, (first: CreditsRecord<N>, second: CreditsRecord<N>), (CreditsRecord<N>) },
+        { split, (input: CreditsRecord<N>, microcredits: u64), (CreditsRecord<N>, CreditsRecord<N>) },
+        { bond_public, (validator: Address<N>, withdrawal: Address<N>, microcredits: u64), (Future<N>) },
+        ],
+        [{ CreditsRecord("credits"), (microcredits: u64) }],
+        [
+        { bonded, Address<N> => BondState<N> },
+        { account, Address<N> => u64 },
+        ],
+    }
+}
+
+impl<N: AleoNetwork> Credits<N> {
+    /// Binds to `credits.aleo` on the given backend, paying fees from the public balance.
+    pub fn new(backend: impl Backend<N> + 'static) -> Result<Self> {
+        Ok(Self::bind(
+            Program::credits()?,
+            std::sync::Arc::new(backend),
+            FeeStrategy::default(),
+        ))
+    }
+}
@@ -0,0 +1,265 @@
+use crate::bindings::*;
+use crate::*;
+use std::time::Instant;
+
+pub mod node;
+
+/// The private key of the first development validator, which snarkOS funds in the genesis block.
+pub const DEV_PRIVATE_KEY: &str = "APrivateKey1zkp8CZNn3yeCseEtxuVPbDCwSyhGW6yZKUYKfgXmcpoGPWH";
+
+/// A snarkOS development node reachable over HTTP.
+#[derive(Clone, Debug, Default)]
+pub struct Devnet {
+    /// Where the node is reachable, and how long to wait for it.
+    config: LeologyConfig,
+}
+
+impl Devnet {
+    /// Initializes a handle to the node at the given endpoint, with the default configuration.
+    pub fn new(endpoint: impl Into<String>) -> Self {
+        Self::with_config(LeologyConfig {
+            endpoint: endpoint.into(),
+            ..Default::default()
+        })
+    }
+
+    /// Initializes a handle to the node described by the configuration.
+    pub fn with_config(config: LeologyConfig) -> Self {
+        Self { config }
+    }
+
+    /// Initializes a handle to the node configured by `leology.toml`, `.env` and the environment.
+    pub fn from_env() -> Result<Self> {
+        Ok(Self::with_config(LeologyConfig::load()?))
+    }
+
+    /// Sets how long to wait for broadcast transactions to be included in a block.
+    pub fn with_confirmation_policy(mut self, confirmation_policy: ConfirmationPolicy) -> Self {
+        self.config.confirmation_timeout = confirmation_policy.timeout;
+        self.config.poll_interval = confirmation_policy.interval;
+        self
+    }
+
+    /// Returns the configuration of the handle.
+    pub fn config(&self) -> &LeologyConfig {
+        &self.config
+    }
+
+    /// Returns the base URL of the node's REST API.
+    pub fn endpoint(&self) -> &str {
+        &self.config.endpoint
+    }
+
+    /// Returns the configuration, failing if it targets another network than `N`.
+    ///
+    /// The network segment of the REST API paths comes from the configuration, so querying it
+    /// with the types of another network would silently read the wrong ledger.
+    pub fn config_for<N: AleoNetwork>(&self) -> Result<&LeologyConfig> {
+        ensure!(
+            self.config.network == N::SHORT_NAME,
+            "The devnet at {} is configured for the '{}' network, but is used with '{}'",
+            self.endpoint(),
+            self.config.network,
+            N::SHORT_NAME
+        );
+        Ok(&self.config)
+    }
+
+    /// Queries the health of the node, failing if it is unreachable.
+    ///
+    /// The deployed programs are left out, see [`Devnet::deployed_programs`].
+    pub fn status<N: AleoNetwork>(&self) -> Result<DevnetStatus<N>> {
+        let config = self.config_for::<N>()?;
+        let height = fetch_latest_height(config)
+            .with_context(|| format!("The devnet at {} is unreachable", self.endpoint()))?;
+        Ok(DevnetStatus {
+            height,
+            state_root: fetch_state_root::<N>(config)?,
+            peers: fetch_peer_count(config)?,
+            validators: fetch_committee::<N>(config)?.num_members(),
+            programs: None,
+        })
+    }
+
+    /// Returns the programs deployed after genesis, in the order they were deployed.
+    ///
+    /// The node has no endpoint listing programs, so this fetches every block: it takes one
+    /// request per block, which grows slow on a long-running devnet.
+    pub fn deployed_programs<N: AleoNetwork>(&self) -> Result<Vec<ProgramID<N>>> {
+        let mut programs = Vec::new();
+        for height in 0..=Backend::<N>::get_latest_height(self)? {
+            let block = Backend::<N>::get_block(self, height)?;
+            programs.extend(
+                block
+                    .transactions()
+                    .iter()
+                    .filter_map(|transaction| transaction.deployment())
+                    .map(|deployment| *deployment.program_id()),
+            );
+        }
+        Ok(programs)
+    }
+}
+
+/// Polls the configured endpoint until the node produces blocks, and returns the latest height.
+///
+/// The node is ready once its latest height increased since it first answered, which tells a
+/// booting node apart from one that serves its ledger without extending it. Fails after the
+/// configured `ready_timeout`.
+pub fn wait_until_ready(config: &LeologyConfig) -> Result<u32> {
+    let (endpoint, timeout) = (&config.endpoint, config.ready_timeout);
+    let start = Instant::now();
+    let mut first_height = None;
+    loop {
+        match (fetch_latest_height(config), first_height) {
+            (Ok(height), Some(first_height)) if height > first_height => return Ok(height),
+            (Ok(height), None) => first_height = Some(height),
+            _ => {}
+        }
+        if start.elapsed() >= timeout {
+            match first_height {
+                Some(height) => bail!(
+                    "❌ The devnet at {endpoint} is stuck at block {height} after {timeout:?}"
+                ),
+                None => bail!("❌ The devnet at {endpoint} is unreachable after {timeout:?}"),
+            }
+        }
+        sleep(config.poll_interval);
+    }
+}
+
+/// Polls the configured endpoint until the node produced `blocks` more blocks, and returns the
+/// latest height.
+///
+/// snarkOS produces blocks on its own schedule, so this waits for them rather than producing
+/// them. Fails if no block is produced for the configured `ready_timeout`.
+pub fn wait_for_blocks(config: &LeologyConfig, blocks: u32) -> Result<u32> {
+    let mut height = fetch_latest_height(config)?;
+    let target = height.saturating_add(blocks);
+    let mut last_progress = Instant::now();
+    while height < target {
+        if last_progress.elapsed() >= config.ready_timeout {
+            bail!(
+                "❌ The devnet at {} is stuck at block {height} after {:?}",
+                config.endpoint,
+                config.ready_timeout
+            );
+        }
+        sleep(config.poll_interval);
+        let latest_height = fetch_latest_height(config)?;
+        if latest_height > height {
+            height = latest_height;
+            last_progress = Instant::now();
+        }
+    }
+    Ok(height)
+}
+
+/// A snapshot of the health of a devnet, returned by [`Devnet::status`].
+#[derive(Clone, Debug)]
+pub struct DevnetStatus<N: Network> {
+    /// The height of the latest block.
+    pub height: u32,
+    /// The latest state root.
+    pub state_root: <N as Network>::StateRoot,
+    /// The number of peers connected to the node.
+    pub peers: usize,
+    /// The number of validators in the latest committee.
+    pub validators: usize,
+    /// The programs deployed after genesis, in the order they were deployed, if they were listed.
+    pub programs: Option<Vec<ProgramID<N>>>,
+}
+
+impl<N: Network> std::fmt::Display for DevnetStatus<N> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "Height:     {}", self.height)?;
+        writeln!(f, "State root: {}", self.state_root)?;
+        writeln!(f, "Peers:      {}", self.peers)?;
+        write!(f, "Validators: {}", self.validators)?;
+        if let Some(programs) = &self.programs {
+            write!(f, "\nPrograms:   {}", programs.len())?;
+            for program_id in programs {
+                write!(f, "\n  - {program_id}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<N: AleoNetwork> Backend<N> for Devnet {
+    fn find_program(&self, program_id: &ProgramID<N>) -> Result<Option<Program<N>>> {
+        find_program(program_id, self.config_for::<N>()?)
+    }
+
+    fn get_mapping_value(
+        &self,
+        program_id: &ProgramID<N>,
+        mapping_name: &Identifier<N>,
+        key: &Plaintext<N>,
+    ) -> Result<Option<Value<N>>> {
+        get_mapping_value(program_id, mapping_name, key, self.config_for::<N>()?)
+    }
+
+    fn query(&self) -> Query<N, BlockMemory<N>> {
+        Query::from(self.endpoint())
+    }
+
+    fn broadcast_transaction(&self, transaction: Transaction<N>) -> Result<Confirmation<N>> {
+        broadcast_transaction(transaction, self.config_for::<N>()?)
+    }
+
+    fn get_latest_height(&self) -> Result<u32> {
+        fetch_latest_height(self.config_for::<N>()?)
+    }
+
+    fn get_block(&self, height: u32) -> Result<Block<N>> {
+        fetch_block_at_height(height, self.config_for::<N>()?)
+    }
+
+    fn contains_serial_number(&self, serial_number: &Field<N>) -> Result<bool> {
+        contains_serial_number(serial_number, self.config_for::<N>()?)
+    }
+
+    fn genesis_account(&self) -> Result<Account<N>> {
+        Account::try_from(DEV_PRIVATE_KEY)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_wait_until_ready_unreachable() {
+        // Find a port nothing listens on.
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let config = LeologyConfig {
+            endpoint: format!("http://{}", listener.local_addr().unwrap()),
+            ready_timeout: Duration::ZERO,
+            ..Default::default()
+        };
+        drop(listener);
+
+        let error = wait_until_ready(&config).unwrap_err();
+        assert!(error.to_string().contains("unreachable"));
+    }
+
+    #[test]
+    fn test_wait_for_blocks() {
+        let responses = ["5", "5", "6", "7"].map(|height| ("200 OK", height.to_string()));
+        let config = crate::bindings::tests::serve(responses.into());
+        assert_eq!(wait_for_blocks(&config, 2).unwrap(), 7);
+    }
+
+    #[test]
+    fn test_network_mismatch() {
+        // The network is checked before any request is sent.
+        let devnet = Devnet::with_config(LeologyConfig {
+            endpoint: "http://127.0.0.1:1".to_string(),
+            ..Default::default()
+        });
+        assert!(devnet.config_for::<TestnetV0>().is_ok());
+        let error = Backend::<MainnetV0>::get_latest_height(&devnet).unwrap_err();
+        assert!(error.to_string().contains("'mainnet'"));
+    }
+}
@@ -0,0 +1,375 @@
+//! Spawns and stops a single-validator snarkOS devnet as a background process.
+//!
+//! Everything the node writes lives in a data directory (`.leology` by default):
+//!
+//! - `snarkos.pid` holds the ID of the running validator process,
+//! - `logs/` holds one log file per run,
+//! - `snapshots/` holds one copy of the ledger per snapshot,
+//! - the ledger of the validator.
+
+use crate::*;
+use std::fs::{self, File};
+use std::path::{Path, PathBuf};
+use std::process::{Child, Command, Stdio};
+use std::thread::sleep;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+/// The default directory holding the pidfile, the logs and the ledger of the devnet.
+pub const DEFAULT_DATA_DIR: &str = ".leology";
+/// The snarkOS repository built when no binary is provided.
+pub const SNARKOS_REPOSITORY: &str = "https://github.com/leology-org/snarkOS";
+/// The branch of [`SNARKOS_REPOSITORY`] that is built.
+pub const SNARKOS_BRANCH: &str = "minimal";
+/// How long to wait for the validator to exit after asking it to stop.
+const STOP_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Where the devnet keeps its files, and which snarkOS binary runs it.
+#[derive(Clone, Debug)]
+pub struct NodeOptions {
+    /// The directory holding the pidfile, the logs and the ledger.
+    pub data_dir: PathBuf,
+    /// The snarkOS binary, or `None` to clone and build [`SNARKOS_REPOSITORY`].
+    pub snarkos: Option<PathBuf>,
+}
+
+impl Default for NodeOptions {
+    fn default() -> Self {
+        Self {
+            data_dir: PathBuf::from(DEFAULT_DATA_DIR),
+            snarkos: None,
+        }
+    }
+}
+
+impl NodeOptions {
+    /// Returns the path of the file holding the ID of the validator process.
+    pub fn pid_file(&self) -> PathBuf {
+        self.data_dir.join("snarkos.pid")
+    }
+
+    /// Returns the directory holding the log files.
+    pub fn logs_dir(&self) -> PathBuf {
+        self.data_dir.join("logs")
+    }
+
+    /// Returns the directory holding the copies of the ledger saved by [`snapshot`].
+    pub fn snapshots_dir(&self) -> PathBuf {
+        self.data_dir.join("snapshots")
+    }
+
+    /// Returns `true` if the entry of the data directory is not part of the ledger.
+    fn is_bookkeeping(&self, path: &Path) -> bool {
+        path == self.pid_file() || path == self.logs_dir() || path == self.snapshots_dir()
+    }
+}
+
+/// A validator process spawned by [`start`].
+#[derive(Debug)]
+pub struct NodeProcess {
+    /// The validator process.
+    pub child: Child,
+    /// The file the validator logs to.
+    pub log_file: PathBuf,
+}
+
+/// Starts a fresh single-validator devnet in the background, and records its process ID.
+pub fn start(options: &NodeOptions) -> Result<NodeProcess> {
+    launch(options, true)
+}
+
+/// Restarts the devnet in the background on the ledger it left, and records its process ID.
+pub fn resume(options: &NodeOptions) -> Result<NodeProcess> {
+    launch(options, false)
+}
+
+/// Starts the devnet in the background, from an empty ledger if `clean` is set.
+fn launch(options: &NodeOptions, clean: bool) -> Result<NodeProcess> {
+    if let Some(pid) = running_pid(options)? {
+        bail!("The devnet is already running (pid {pid})");
+    }
+    let snarkos = match &options.snarkos {
+        Some(snarkos) => snarkos.clone(),
+        None => build_snarkos()?,
+    };
+    fs::create_dir_all(options.logs_dir())?;
+    // snarkOS keeps its ledger in the working directory.
+    let data_dir = fs::canonicalize(&options.data_dir)?;
+
+    if clean {
+        let status = Command::new(&snarkos)
+            .args(["clean", "--dev", "0"])
+            .current_dir(&data_dir)
+            .stdout(Stdio::null())
+            .status()?;
+        ensure!(status.success(), "Failed to clean the devnet ledger");
+    }
+
+    let timestamp = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
+    let logs_dir = data_dir.join("logs");
+    let log_file = logs_dir.join(format!("validator-{timestamp}.log"));
+    // snarkOS writes its log itself, so the console output goes to a file of its own.
+    let output = File::create(logs_dir.join(format!("validator-{timestamp}.out")))?;
+    let mut command = Command::new(&snarkos);
+    command
+        .args([
+            "start",
+            "--nodisplay",
+            "--dev",
+            "0",
+            "--dev-num-validators",
+            "1",
+        ])
+        .arg("--validator")
+        .arg("--logfile")
+        .arg(&log_file)
+        .current_dir(&data_dir)
+        .stdin(Stdio::null())
+        .stdout(output.try_clone()?)
+        .stderr(output);
+    // Detach the validator from the terminal, so that it outlives the CLI.
+    #[cfg(unix)]
+    std::os::unix::process::CommandExt::process_group(&mut command, 0);
+    let child = command
+        .spawn()
+        .map_err(|e| anyhow!("Failed to spawn '{}' - {e}", snarkos.display()))?;
+    fs::write(options.pid_file(), child.id().to_string())?;
+    Ok(NodeProcess { child, log_file })
+}
+
+/// Stops the devnet started by [`start`], if any, and returns the ID of the stopped process.
+pub fn stop(options: &NodeOptions) -> Result<Option<u32>> {
+    let Some(pid) = running_pid(options)? else {
+        return Ok(None);
+    };
+    signal(pid, "TERM")?;
+    let start = Instant::now();
+    while is_alive(pid)? {
+        if start.elapsed() >= STOP_TIMEOUT {
+            signal(pid, "KILL")?;
+            break;
+        }
+        sleep(Duration::from_millis(200));
+    }
+    fs::remove_file(options.pid_file())?;
+    Ok(Some(pid))
+}
+
+/// Returns the ID of the running validator process, removing the pidfile if the process exited.
+pub fn running_pid(options: &NodeOptions) -> Result<Option<u32>> {
+    let pid_file = options.pid_file();
+    if !pid_file.exists() {
+        return Ok(None);
+    }
+    let pid: u32 = fs::read_to_string(&pid_file)?
+        .trim()
+        .parse()
+        .map_err(|e| anyhow!("Invalid pidfile '{}' - {e}", pid_file.display()))?;
+    match is_alive(pid)? {
+        true => Ok(Some(pid)),
+        false => {
+            fs::remove_file(&pid_file)?;
+            Ok(None)
+        }
+    }
+}
+
+/// Copies the ledger of the devnet into a new snapshot, and returns its ID.
+///
+/// A running devnet is stopped while its ledger is copied, and resumed afterwards.
+pub fn snapshot(options: &NodeOptions) -> Result<SnapshotId> {
+    let mut ids = Vec::new();
+    if options.snapshots_dir().is_dir() {
+        for entry in fs::read_dir(options.snapshots_dir())? {
+            if let Ok(id) = entry?.file_name().to_string_lossy().parse() {
+                ids.push(id);
+            }
+        }
+    }
+    let snapshot = SnapshotId(ids.into_iter().max().map_or(0, |id: u32| id + 1));
+    let is_running = stop(options)?.is_some();
+    let snapshot_dir = options.snapshots_dir().join(snapshot.to_string());
+    fs::create_dir_all(&snapshot_dir)?;
+    for entry in fs::read_dir(&options.data_dir)? {
+        let path = entry?.path();
+        if !options.is_bookkeeping(&path) {
+            copy_recursively(&path, &snapshot_dir.join(entry_name(&path)?))?;
+        }
+    }
+    if is_running {
+        resume(options)?;
+    }
+    Ok(snapshot)
+}
+
+/// Replaces the ledger of the devnet with the copy saved by [`snapshot`].
+///
+/// A running devnet is stopped while its ledger is replaced, and resumed afterwards. The snapshot
+/// is kept, so it can be reverted to several times.
+pub fn revert(options: &NodeOptions, snapshot: SnapshotId) -> Result<()> {
+    let snapshot_dir = options.snapshots_dir().join(snapshot.to_string());
+    ensure!(snapshot_dir.is_dir(), "Snapshot {snapshot} does not exist");
+    let is_running = stop(options)?.is_some();
+    for entry in fs::read_dir(&options.data_dir)? {
+        let path = entry?.path();
+        if options.is_bookkeeping(&path) {
+            continue;
+        }
+        match path.is_dir() {
+            true => fs::remove_dir_all(&path)?,
+            false => fs::remove_file(&path)?,
+        }
+    }
+    for entry in fs::read_dir(&snapshot_dir)? {
+        let path = entry?.path();
+        copy_recursively(&path, &options.data_dir.join(entry_name(&path)?))?;
+    }
+    if is_running {
+        resume(options)?;
+    }
+    Ok(())
+}
+
+/// Copies the file, or the directory and its content, to `destination`.
+fn copy_recursively(source: &Path, destination: &Path) -> Result<()> {
+    if !source.is_dir() {
+        fs::copy(source, destination)?;
+        return Ok(());
+    }
+    fs::create_dir_all(destination)?;
+    for entry in fs::read_dir(source)? {
+        let path = entry?.path();
+        copy_recursively(&path, &destination.join(entry_name(&path)?))?;
+    }
+    Ok(())
+}
+
+/// Returns the last component of the path.
+fn entry_name(path: &Path) -> Result<&std::ffi::OsStr> {
+    path.file_name()
+        .ok_or_else(|| anyhow!("'{}' has no file name", path.display()))
+}
+
+/// Returns `true` if the process with the given ID exists.
+fn is_alive(pid: u32) -> Result<bool> {
+    let status = Command::new("kill")
+        .args(["-0", &pid.to_string()])
+        .stderr(Stdio::null())
+        .status()?;
+    Ok(status.success())
+}
+
+/// Sends the signal with the given name to the process.
+fn signal(pid: u32, signal: &str) -> Result<()> {
+    let status = Command::new("kill")
+        .args([&format!("-{signal}"), &pid.to_string()])
+        .status()?;
+    ensure!(
+        status.success(),
+        "Failed to send SIG{signal} to process {pid}"
+    );
+    Ok(())
+}
+
+/// Clones and builds [`SNARKOS_REPOSITORY`] unless it was already built, and returns the binary.
+fn build_snarkos() -> Result<PathBuf> {
+    let checkout = std::env::temp_dir().join("snarkos");
+    let snarkos = checkout.join("target").join("release").join("snarkos");
+    if snarkos.is_file() {
+        return Ok(snarkos);
+    }
+    if !checkout.join("Cargo.toml").is_file() {
+        run(Command::new("git")
+            .args(["clone", "--branch", SNARKOS_BRANCH, "--depth", "1"])
+            .arg(SNARKOS_REPOSITORY)
+            .arg(&checkout))?;
+    }
+    run(Command::new("cargo")
+        .args(["build", "--release", "--features", "minimal"])
+        .current_dir(&checkout))?;
+    Ok(snarkos)
+}
+
+/// Runs the command in the foreground, failing if it does not succeed.
+fn run(command: &mut Command) -> Result<()> {
+    let status = command.status()?;
+    ensure!(status.success(), "{command:?} failed with {status}");
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stale_pid_file() {
+        let options = NodeOptions {
+            data_dir: std::env::temp_dir().join(format!("leology-node-{}", std::process::id())),
+            snarkos: None,
+        };
+        fs::create_dir_all(&options.data_dir).unwrap();
+        assert_eq!(running_pid(&options).unwrap(), None);
+
+        // A pidfile naming a process that exited is removed.
+        let mut child = Command::new("true").spawn().unwrap();
+        child.wait().unwrap();
+        fs::write(options.pid_file(), child.id().to_string()).unwrap();
+        assert_eq!(running_pid(&options).unwrap(), None);
+        assert!(!options.pid_file().exists());
+
+        // A running process is reported and stopped.
+        let mut child = Command::new("sleep").arg("30").spawn().unwrap();
+        let pid = child.id();
+        let waiter = std::thread::spawn(move || child.wait());
+        fs::write(options.pid_file(), pid.to_string()).unwrap();
+        assert_eq!(running_pid(&options).unwrap(), Some(pid));
+        assert_eq!(stop(&options).unwrap(), Some(pid));
+        assert!(!options.pid_file().exists());
+        assert!(!waiter.join().unwrap().unwrap().success());
+        fs::remove_dir_all(&options.data_dir).unwrap();
+    }
+
+    #[test]
+    fn test_snapshot_revert() {
+        let options = NodeOptions {
+            data_dir: std::env::temp_dir().join(format!("leology-snapshot-{}", std::process::id())),
+            snarkos: None,
+        };
+        let ledger_dir = options.data_dir.join(".ledger-1-0");
+        fs::create_dir_all(&ledger_dir).unwrap();
+        fs::create_dir_all(options.logs_dir()).unwrap();
+        fs::write(ledger_dir.join("blocks"), "genesis").unwrap();
+
+        let first = snapshot(&options).unwrap();
+        fs::write(ledger_dir.join("blocks"), "genesis, 1").unwrap();
+        fs::write(options.data_dir.join(".current-proposal-cache-1-0"), "1").unwrap();
+        let second = snapshot(&options).unwrap();
+        assert_eq!((first, second), (SnapshotId(0), SnapshotId(1)));
+        assert!(!options.snapshots_dir().join("0").join("logs").exists());
+
+        fs::write(ledger_dir.join("blocks"), "genesis, 1, 2").unwrap();
+        revert(&options, first).unwrap();
+        assert_eq!(
+            fs::read_to_string(ledger_dir.join("blocks")).unwrap(),
+            "genesis"
+        );
+        assert!(!options
+            .data_dir
+            .join(".current-proposal-cache-1-0")
+            .exists());
+        assert!(options.logs_dir().is_dir());
+        revert(&options, second).unwrap();
+        assert_eq!(
+            fs::read_to_string(ledger_dir.join("blocks")).unwrap(),
+            "genesis, 1"
+        );
+        assert!(revert(&options, SnapshotId(2)).is_err());
+        fs::remove_dir_all(&options.data_dir).unwrap();
+    }
+
+    #[test]
+    fn test_paths() {
+        let options = NodeOptions::default();
+        assert_eq!(options.pid_file(), Path::new(".leology/snarkos.pid"));
+        assert_eq!(options.logs_dir(), Path::new(".leology/logs"));
+    }
+}
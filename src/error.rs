@@ -0,0 +1,48 @@
+use std::fmt;
+
+/// Errors raised by leology when a transaction does not have the expected on-chain effect, or
+/// when its results do not match the generated bindings.
+///
+/// The generated bindings return these wrapped in an [`anyhow::Error`], so tests can assert on
+/// them with `error.downcast_ref::<LeologyError>()`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum LeologyError {
+    /// The transaction was included in a block, but its finalize logic failed and was reverted.
+    FinalizeRejected {
+        /// The ID of the broadcast transaction.
+        transaction_id: String,
+        /// A description of the rejected deployment or execution.
+        reason: String,
+    },
+    /// The transaction was dropped from the block without being applied.
+    Aborted {
+        /// The ID of the broadcast transaction.
+        transaction_id: String,
+    },
+    /// A value could not be converted into the Rust type of its binding.
+    UnexpectedValue {
+        /// The type the binding expects.
+        expected: String,
+        /// The type of the value that was found instead.
+        found: String,
+    },
+}
+
+impl fmt::Display for LeologyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::FinalizeRejected {
+                transaction_id,
+                reason,
+            } => write!(f, "❌ Transaction {transaction_id} was rejected: {reason}"),
+            Self::Aborted { transaction_id } => {
+                write!(f, "❌ Transaction {transaction_id} was aborted")
+            }
+            Self::UnexpectedValue { expected, found } => {
+                write!(f, "Expected a value of type {expected}, found {found}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for LeologyError {}
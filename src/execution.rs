@@ -0,0 +1,217 @@
+use crate::bindings::*;
+use crate::*;
+
+/// The decoded inputs and outputs of one transition of an execution.
+#[derive(Clone, Debug)]
+pub struct TransitionReport<N: Network> {
+    /// The ID of the transition.
+    pub id: <N as Network>::TransitionID,
+    /// The function executed by the transition.
+    pub locator: Locator<N>,
+    /// The inputs of the transition, or `None` for the inputs that are not revealed on-chain,
+    /// such as input records.
+    pub inputs: Vec<Option<Value<N>>>,
+    /// The outputs of the transition, or `None` for the outputs that cannot be decrypted by the
+    /// account, such as records owned by another address.
+    pub outputs: Vec<Option<Value<N>>>,
+}
+
+impl<N: Network> TransitionReport<N> {
+    /// Decodes the transition, decrypting its private values with the view key of the account
+    /// that signed the execution.
+    pub fn decode(transition: &Transition<N>, view_key: &ViewKey<N>) -> Result<Self> {
+        let function_id = compute_function_id(
+            &U16::new(N::ID),
+            transition.program_id(),
+            transition.function_name(),
+        )?;
+        let tvk = transition_view_key(transition.tpk(), view_key);
+        // Private values are encrypted under `Hash(function ID || tvk || index)`.
+        let decrypt = |ciphertext: &Ciphertext<N>, index: usize| -> Option<Value<N>> {
+            let index = Field::from_u16(u16::try_from(index).ok()?);
+            let plaintext_view_key = N::hash_psd4(&[function_id, tvk, index]).ok()?;
+            ciphertext
+                .decrypt_symmetric(plaintext_view_key)
+                .ok()
+                .map(Value::Plaintext)
+        };
+
+        let inputs = transition
+            .inputs()
+            .iter()
+            .enumerate()
+            .map(|(index, input)| match input {
+                Input::Constant(_, plaintext) | Input::Public(_, plaintext) => {
+                    plaintext.clone().map(Value::Plaintext)
+                }
+                Input::Private(_, ciphertext) => {
+                    ciphertext.as_ref().and_then(|c| decrypt(c, index))
+                }
+                Input::Record(..) | Input::ExternalRecord(_) => None,
+            })
+            .collect();
+
+        // Output indices follow the input indices.
+        let num_inputs = transition.inputs().len();
+        let outputs = transition
+            .outputs()
+            .iter()
+            .enumerate()
+            .map(|(index, output)| match output {
+                Output::Constant(_, plaintext) | Output::Public(_, plaintext) => {
+                    plaintext.clone().map(Value::Plaintext)
+                }
+                Output::Private(_, ciphertext) => ciphertext
+                    .as_ref()
+                    .and_then(|c| decrypt(c, num_inputs + index)),
+                Output::Record(_, _, record) => record
+                    .as_ref()
+                    .and_then(|record| record.decrypt(view_key).ok())
+                    .map(Value::Record),
+                Output::ExternalRecord(_) => None,
+                Output::Future(_, future) => future.clone().map(Value::Future),
+            })
+            .collect();
+
+        Ok(Self {
+            id: *transition.id(),
+            locator: Locator::new(*transition.program_id(), *transition.function_name()),
+            inputs,
+            outputs,
+        })
+    }
+}
+
+/// Derives the transition view key `(tpk * view_key).x`, which the signer used to encrypt the
+/// private inputs and outputs of the transition.
+fn transition_view_key<N: Network>(tpk: &Group<N>, view_key: &ViewKey<N>) -> Field<N> {
+    (*tpk * **view_key).to_x_coordinate()
+}
+
+/// The decoded transitions of an execution, in the order they were executed.
+///
+/// Calls to imported programs are executed first, so the transition of the invoked function
+/// is the last one.
+#[derive(Clone, Debug)]
+pub struct ExecutionReport<N: Network> {
+    /// The ID of the execution transaction.
+    pub transaction_id: <N as Network>::TransactionID,
+    /// The decoded transitions.
+    pub transitions: Vec<TransitionReport<N>>,
+}
+
+impl<N: Network> ExecutionReport<N> {
+    /// Decodes every transition of the execution transaction with the signer's view key.
+    pub fn decode(transaction: &Transaction<N>, view_key: &ViewKey<N>) -> Result<Self> {
+        let execution = transaction
+            .execution()
+            .ok_or_else(|| anyhow!("Transaction {} is not an execution", transaction.id()))?;
+        let transitions = execution
+            .transitions()
+            .map(|transition| TransitionReport::decode(transition, view_key))
+            .collect::<Result<_>>()?;
+        Ok(Self {
+            transaction_id: transaction.id(),
+            transitions,
+        })
+    }
+
+    /// Decodes the execution like [`decode`](Self::decode), completing the outputs of the invoked
+    /// function with the response computed when the execution was authorized.
+    ///
+    /// The response holds the plaintext of every output of the invoked function, including the
+    /// records sent to other addresses and the records returned by imported programs, which
+    /// the signer cannot decrypt from the transaction.
+    pub fn decode_with_response(
+        transaction: &Transaction<N>,
+        view_key: &ViewKey<N>,
+        response: &Response<N>,
+    ) -> Result<Self> {
+        let mut report = Self::decode(transaction, view_key)?;
+        let root = report
+            .transitions
+            .last_mut()
+            .ok_or_else(|| anyhow!("Transaction {} has no transition", transaction.id()))?;
+        ensure!(
+            root.outputs.len() == response.outputs().len(),
+            "The response of '{}' has {} outputs, but its transition has {}",
+            root.locator,
+            response.outputs().len(),
+            root.outputs.len()
+        );
+        for (output, value) in root.outputs.iter_mut().zip_eq(response.outputs()) {
+            if output.is_none() {
+                *output = Some(value.clone());
+            }
+        }
+        Ok(report)
+    }
+
+    /// Returns the transition of the invoked function.
+    pub fn root(&self) -> Option<&TransitionReport<N>> {
+        self.transitions.last()
+    }
+
+    /// Returns the last transition executing the given function.
+    pub fn transition(&self, locator: &Locator<N>) -> Option<&TransitionReport<N>> {
+        self.transitions
+            .iter()
+            .rev()
+            .find(|transition| &transition.locator == locator)
+    }
+
+    /// Returns the outputs of the last transition executing the given function, failing if one
+    /// of them cannot be decrypted.
+    pub fn outputs(&self, locator: &Locator<N>) -> Result<Vec<Value<N>>> {
+        let transition = self.transition(locator).ok_or_else(|| {
+            anyhow!(
+                "Transaction {} does not execute '{locator}'",
+                self.transaction_id
+            )
+        })?;
+        transition
+            .outputs
+            .iter()
+            .enumerate()
+            .map(|(index, output)| {
+                output.clone().ok_or_else(|| {
+                    anyhow!("Output {index} of '{locator}' cannot be decrypted by the caller")
+                })
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use snarkvm::utilities::TestRng;
+
+    #[test]
+    fn test_transition_view_key() {
+        let rng = &mut TestRng::default();
+        let account = Account::<Nw>::new(rng).unwrap();
+        let request = Request::sign(
+            account.private_key(),
+            ProgramID::from_str("credits.aleo").unwrap(),
+            Identifier::from_str("transfer_public").unwrap(),
+            [
+                Value::<Nw>::from_str(&account.address().to_string()).unwrap(),
+                Value::from_str("1u64").unwrap(),
+            ]
+            .into_iter(),
+            &[
+                ValueType::from_str("address.public").unwrap(),
+                ValueType::from_str("u64.public").unwrap(),
+            ],
+            None,
+            true,
+            rng,
+        )
+        .unwrap();
+        assert_eq!(
+            transition_view_key(&request.to_tpk(), account.view_key()),
+            *request.tvk()
+        );
+    }
+}
@@ -0,0 +1,213 @@
+use crate::bindings::*;
+use crate::*;
+
+/// How a deployment or execution pays its fee.
+#[derive(Clone, Debug, Default)]
+pub enum FeeStrategy<N: Network> {
+    /// Pay from the public balance in `credits.aleo/account`.
+    #[default]
+    Public,
+    /// Pay from the given `credits.aleo` record.
+    ///
+    /// The record is spent by the first transaction paying with it, so later fees are paid as
+    /// with [`FeeStrategy::AutoRecord`].
+    Record(Box<Record<N, Plaintext<N>>>),
+    /// Pay from the smallest unspent `credits.aleo` record of the account that covers the fee.
+    AutoRecord,
+}
+
+impl<N: Network> FeeStrategy<N> {
+    /// Authorizes the payment of `base_fee + priority_fee` microcredits by the account of the
+    /// wallet, for the deployment or execution with the given ID.
+    pub fn authorize<C: ConsensusStorage<N>, R: Rng + CryptoRng>(
+        &self,
+        vm: &VM<N, C>,
+        wallet: &Wallet<N>,
+        base_fee: u64,
+        priority_fee: u64,
+        deployment_or_execution_id: Field<N>,
+        rng: &mut R,
+    ) -> Result<Authorization<N>> {
+        let account = wallet.account();
+        let total_fee = base_fee.saturating_add(priority_fee);
+        let record = match self {
+            Self::Public => {
+                let public_balance = wallet.backend().get_public_balance(&account.address())?;
+                ensure!(
+                    public_balance >= total_fee,
+                    "❌ The public balance of {} ({public_balance} microcredits) is insufficient to pay a fee of {total_fee} microcredits",
+                    account.address()
+                );
+                return vm.authorize_fee_public(
+                    account.private_key(),
+                    base_fee,
+                    priority_fee,
+                    deployment_or_execution_id,
+                    rng,
+                );
+            }
+            Self::Record(record) if is_spent(wallet, record)? => {
+                select_fee_record(wallet, total_fee)?
+            }
+            Self::Record(record) => {
+                let microcredits = record_microcredits(record)?;
+                ensure!(
+                    microcredits >= total_fee,
+                    "❌ The fee record holds {microcredits} microcredits, which is insufficient to pay a fee of {total_fee} microcredits"
+                );
+                record.as_ref().clone()
+            }
+            Self::AutoRecord => select_fee_record(wallet, total_fee)?,
+        };
+        vm.authorize_fee_private(
+            account.private_key(),
+            record,
+            base_fee,
+            priority_fee,
+            deployment_or_execution_id,
+            rng,
+        )
+    }
+}
+
+/// The fee of an execution, in microcredits.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct FeeEstimate {
+    /// The cost of storing the execution, proportional to its size.
+    pub storage_cost: u64,
+    /// The cost of running the finalize logic of the execution.
+    pub finalize_cost: u64,
+    /// The additional fee paid to prioritize the execution.
+    pub priority_fee: u64,
+}
+
+impl FeeEstimate {
+    /// Estimates the fee of the execution at the given block height, with the given priority fee.
+    pub fn for_execution<N: Network>(
+        process: &Process<N>,
+        execution: &Execution<N>,
+        height: u32,
+        priority_fee: u64,
+    ) -> Result<Self> {
+        let (_, (storage_cost, finalize_cost)) = match N::CONSENSUS_VERSION(height)? {
+            ConsensusVersion::V1 => execution_cost_v1(process, execution)?,
+            _ => execution_cost_v2(process, execution)?,
+        };
+        Ok(Self {
+            storage_cost,
+            finalize_cost,
+            priority_fee,
+        })
+    }
+
+    /// Returns the minimum fee required by the network.
+    pub fn base_fee(&self) -> u64 {
+        self.storage_cost.saturating_add(self.finalize_cost)
+    }
+
+    /// Returns the fee paid by the transaction.
+    pub fn total(&self) -> u64 {
+        self.base_fee().saturating_add(self.priority_fee)
+    }
+}
+
+impl std::fmt::Display for FeeEstimate {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} microcredits (storage {}, finalize {}, priority {})",
+            self.total(),
+            self.storage_cost,
+            self.finalize_cost,
+            self.priority_fee
+        )
+    }
+}
+
+/// Returns the microcredits held by a `credits.aleo` record.
+pub fn record_microcredits<N: Network>(record: &Record<N, Plaintext<N>>) -> Result<u64> {
+    let microcredits = Identifier::from_str("microcredits")?;
+    match record.data().get(&microcredits) {
+        Some(entry) => u64::try_from_value(entry.to_value()),
+        None => bail!("The record is not a credits.aleo record"),
+    }
+}
+
+/// Returns `true` if the `credits.aleo` record of the wallet's account was spent.
+fn is_spent<N: Network>(wallet: &Wallet<N>, record: &Record<N, Plaintext<N>>) -> Result<bool> {
+    let serial_number = record_serial_number(
+        record,
+        &ProgramID::from_str("credits.aleo")?,
+        &Identifier::from_str("credits")?,
+        wallet.account(),
+    )?;
+    wallet.is_spent(&serial_number)
+}
+
+/// Returns the smallest unspent `credits.aleo` record of the wallet's account holding at least
+/// `amount`.
+fn select_fee_record<N: Network>(
+    wallet: &Wallet<N>,
+    amount: u64,
+) -> Result<Record<N, Plaintext<N>>> {
+    let credits = ProgramID::<N>::from_str("credits.aleo")?;
+    let mut candidates = Vec::new();
+    for owned in wallet.unspent()? {
+        if owned.program_id != credits {
+            continue;
+        }
+        let microcredits = record_microcredits(&owned.record)?;
+        if microcredits >= amount {
+            candidates.push((microcredits, owned.record));
+        }
+    }
+    candidates
+        .into_iter()
+        .min_by_key(|(microcredits, _)| *microcredits)
+        .map(|(_, record)| record)
+        .ok_or_else(|| {
+            anyhow!(
+                "❌ {} has no unspent credits record holding at least {amount} microcredits",
+                wallet.account().address()
+            )
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use snarkvm::utilities::TestRng;
+
+    #[test]
+    fn test_fee_estimate() {
+        let estimate = FeeEstimate {
+            storage_cost: 1_000,
+            finalize_cost: 250,
+            priority_fee: 5,
+        };
+        assert_eq!(estimate.base_fee(), 1_250);
+        assert_eq!(estimate.total(), 1_255);
+        assert_eq!(
+            estimate.to_string(),
+            "1255 microcredits (storage 1000, finalize 250, priority 5)"
+        );
+    }
+
+    #[test]
+    fn test_record_microcredits() {
+        let account = Account::<Nw>::new(&mut TestRng::default()).unwrap();
+        let record = Record::<Nw, Plaintext<Nw>>::from_str(&format!(
+            "{{ owner: {}.private, microcredits: 100u64.private, _nonce: 0group.public }}",
+            account.address()
+        ))
+        .unwrap();
+        assert_eq!(record_microcredits(&record).unwrap(), 100);
+
+        let record = Record::<Nw, Plaintext<Nw>>::from_str(&format!(
+            "{{ owner: {}.private, amount: 100u64.private, _nonce: 0group.public }}",
+            account.address()
+        ))
+        .unwrap();
+        assert!(record_microcredits(&record).is_err());
+    }
+}
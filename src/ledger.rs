@@ -0,0 +1,324 @@
+use crate::bindings::*;
+use crate::*;
+use rand::SeedableRng;
+use rand_chacha::ChaChaRng;
+use snarkvm::synthesizer::program::FinalizeGlobalState;
+use std::sync::{Arc, RwLock};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// The seed of the RNG sampling the genesis block, so that every ledger starts from the same state.
+const GENESIS_SEED: u64 = 0;
+
+/// An in-memory ledger that deploys, executes and finalizes transactions without a snarkOS node.
+///
+/// Every broadcast transaction is immediately included in a new block, signed by the ledger's
+/// private key. That key also owns the public balance created in the genesis block, so it can be
+/// used to deploy programs and fund test accounts.
+///
+/// The state is shared by the clones of the ledger, so that a [`revert`](Self::revert) is seen by
+/// every program handle holding one.
+#[derive(Clone)]
+pub struct LocalLedger<N: Network> {
+    /// The private key of the validator producing the blocks.
+    private_key: PrivateKey<N>,
+    /// The VM, the latest block and the snapshots.
+    state: Arc<RwLock<LedgerState<N>>>,
+}
+
+/// Identifies a ledger state saved by a snapshot.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct SnapshotId(pub u32);
+
+impl std::fmt::Display for SnapshotId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// The mutable state of a [`LocalLedger`].
+struct LedgerState<N: Network> {
+    /// The VM holding the ledger state.
+    vm: VM<N, ConsensusMemory<N>>,
+    /// The latest block.
+    latest_block: Block<N>,
+    /// The height and hash of the latest block of each snapshot.
+    snapshots: Vec<(u32, N::BlockHash)>,
+    /// The timestamp of the next block, if pinned by `set_next_block_timestamp`.
+    next_timestamp: Option<i64>,
+}
+
+impl<N: Network> LocalLedger<N> {
+    /// Initializes a new ledger, whose genesis block is produced by the given private key.
+    pub fn new(private_key: &PrivateKey<N>) -> Result<Self> {
+        let rng = &mut ChaChaRng::seed_from_u64(GENESIS_SEED);
+        let vm = initialize_vm()?;
+        let genesis = vm.genesis_beacon(private_key, rng)?;
+        vm.add_next_block(&genesis)?;
+        Ok(Self {
+            private_key: *private_key,
+            state: Arc::new(RwLock::new(LedgerState {
+                vm,
+                latest_block: genesis,
+                snapshots: Vec::new(),
+                next_timestamp: None,
+            })),
+        })
+    }
+
+    /// Returns the VM holding the ledger state.
+    ///
+    /// A [`revert`](Self::revert) replaces the VM, so the returned one must not be kept around.
+    pub fn vm(&self) -> VM<N, ConsensusMemory<N>> {
+        self.state.read().unwrap().vm.clone()
+    }
+
+    /// Returns the private key of the validator producing the blocks.
+    pub const fn private_key(&self) -> &PrivateKey<N> {
+        &self.private_key
+    }
+
+    /// Returns the latest block.
+    pub fn latest_block(&self) -> Block<N> {
+        self.state.read().unwrap().latest_block.clone()
+    }
+
+    /// Returns the latest block height.
+    pub fn latest_height(&self) -> u32 {
+        self.state.read().unwrap().latest_block.height()
+    }
+
+    /// Produces a new block containing the given transactions, and adds it to the ledger.
+    pub fn advance_to_next_block(&self, transactions: Vec<Transaction<N>>) -> Result<Block<N>> {
+        let rng = &mut rand::thread_rng();
+        let mut state = self.state.write().unwrap();
+        let block = self.construct_next_block(&state, transactions, rng)?;
+        state.vm.add_next_block(&block)?;
+        state.latest_block = block.clone();
+        state.next_timestamp = None;
+        Ok(block)
+    }
+
+    /// Produces `blocks` empty blocks, and returns the latest block.
+    ///
+    /// Lets finalize logic reading `block.height` run at a later height without waiting.
+    pub fn advance_blocks(&self, blocks: u32) -> Result<Block<N>> {
+        for _ in 0..blocks {
+            self.advance_to_next_block(vec![])?;
+        }
+        Ok(self.latest_block())
+    }
+
+    /// Pins the timestamp, in seconds since the Unix epoch, of the next block.
+    ///
+    /// Blocks are otherwise timestamped with the current time.
+    pub fn set_next_block_timestamp(&self, timestamp: i64) -> Result<()> {
+        let mut state = self.state.write().unwrap();
+        let latest_timestamp = state.latest_block.timestamp();
+        ensure!(
+            timestamp > latest_timestamp,
+            "The next block timestamp must be after the latest one ({latest_timestamp}), got {timestamp}"
+        );
+        state.next_timestamp = Some(timestamp);
+        Ok(())
+    }
+
+    /// Saves the current state of the ledger, to return to it later with [`revert`](Self::revert).
+    pub fn snapshot(&self) -> SnapshotId {
+        let mut state = self.state.write().unwrap();
+        let latest_block = (state.latest_block.height(), state.latest_block.hash());
+        state.snapshots.push(latest_block);
+        SnapshotId(state.snapshots.len() as u32 - 1)
+    }
+
+    /// Returns the ledger to the state saved by the snapshot, discarding the later blocks.
+    ///
+    /// The ledger is rebuilt by replaying its blocks into a fresh VM. A snapshot can be reverted
+    /// to several times, but not once an earlier revert discarded the blocks it was taken at.
+    pub fn revert(&self, snapshot: SnapshotId) -> Result<()> {
+        let mut state = self.state.write().unwrap();
+        let Some(&(height, hash)) = state.snapshots.get(snapshot.0 as usize) else {
+            bail!("Snapshot {snapshot} does not exist");
+        };
+        ensure!(
+            state.vm.block_store().get_block_hash(height)? == Some(hash),
+            "Snapshot {snapshot} was discarded by an earlier revert"
+        );
+        let vm = initialize_vm()?;
+        for height in 0..=height {
+            vm.add_next_block(&block_at(&state.vm, height)?)?;
+        }
+        state.latest_block = block_at(&state.vm, height)?;
+        state.vm = vm;
+        state.next_timestamp = None;
+        Ok(())
+    }
+
+    /// Constructs the beacon block following the latest block, speculating over the transactions.
+    fn construct_next_block<R: Rng + CryptoRng>(
+        &self,
+        state: &LedgerState<N>,
+        transactions: Vec<Transaction<N>>,
+        rng: &mut R,
+    ) -> Result<Block<N>> {
+        let (vm, previous_block) = (&state.vm, &state.latest_block);
+        let next_round = previous_block.round().saturating_add(1);
+        let next_height = previous_block.height().saturating_add(1);
+        // Keep the timestamps strictly increasing, even when several blocks are produced per second.
+        let now = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs() as i64;
+        let next_timestamp = state
+            .next_timestamp
+            .unwrap_or_else(|| now.max(previous_block.timestamp().saturating_add(1)));
+
+        // Calculate the next coinbase targets and timestamps, without any solutions.
+        let (
+            next_coinbase_target,
+            next_proof_target,
+            next_cumulative_proof_target,
+            next_cumulative_weight,
+            next_last_coinbase_target,
+            next_last_coinbase_timestamp,
+        ) = to_next_targets::<N>(
+            previous_block.cumulative_proof_target(),
+            0,
+            previous_block.coinbase_target(),
+            previous_block.cumulative_weight(),
+            previous_block.last_coinbase_target(),
+            previous_block.last_coinbase_timestamp(),
+            next_timestamp,
+        )?;
+        let coinbase_reward = coinbase_reward::<N>(
+            next_height,
+            next_timestamp,
+            N::GENESIS_TIMESTAMP,
+            N::STARTING_SUPPLY,
+            N::ANCHOR_TIME,
+            N::ANCHOR_HEIGHT,
+            N::BLOCK_TIME,
+            0,
+            u64::try_from(previous_block.cumulative_proof_target())?,
+            previous_block.coinbase_target(),
+        )?;
+
+        // Speculate over the transactions, running their finalize logic.
+        let state = FinalizeGlobalState::new::<N>(
+            next_round,
+            next_height,
+            next_cumulative_weight,
+            next_cumulative_proof_target,
+            previous_block.hash(),
+        )?;
+        let solutions = Solutions::<N>::from(None);
+        let (ratifications, transactions, aborted_transaction_ids, ratified_finalize_operations) =
+            vm.speculate(
+                state,
+                next_timestamp.saturating_sub(previous_block.timestamp()),
+                Some(coinbase_reward),
+                vec![],
+                &solutions,
+                transactions.iter(),
+                rng,
+            )?;
+
+        let metadata = Metadata::new(
+            N::ID,
+            next_round,
+            next_height,
+            next_cumulative_weight,
+            next_cumulative_proof_target,
+            next_coinbase_target,
+            next_proof_target,
+            next_last_coinbase_target,
+            next_last_coinbase_timestamp,
+            next_timestamp,
+        )?;
+        let header = Header::from(
+            vm.block_store().current_state_root(),
+            transactions.to_transactions_root()?,
+            transactions.to_finalize_root(ratified_finalize_operations)?,
+            ratifications.to_ratifications_root()?,
+            Field::zero(),
+            Field::zero(),
+            metadata,
+        )?;
+
+        Block::new_beacon(
+            &self.private_key,
+            previous_block.hash(),
+            header,
+            ratifications,
+            solutions,
+            vec![],
+            transactions,
+            aborted_transaction_ids,
+            rng,
+        )
+    }
+}
+
+impl<N: Network> Backend<N> for LocalLedger<N> {
+    fn find_program(&self, program_id: &ProgramID<N>) -> Result<Option<Program<N>>> {
+        let vm = self.vm();
+        match vm.block_store().get_program(program_id)? {
+            Some(program) => Ok(Some(program)),
+            // `credits.aleo` is part of the VM rather than deployed.
+            None => Ok(vm.process().read().get_program(program_id).ok().cloned()),
+        }
+    }
+
+    fn get_mapping_value(
+        &self,
+        program_id: &ProgramID<N>,
+        mapping_name: &Identifier<N>,
+        key: &Plaintext<N>,
+    ) -> Result<Option<Value<N>>> {
+        self.vm()
+            .finalize_store()
+            .get_value_confirmed(*program_id, *mapping_name, key)
+    }
+
+    fn query(&self) -> Query<N, BlockMemory<N>> {
+        Query::from(self.vm().block_store())
+    }
+
+    fn broadcast_transaction(&self, transaction: Transaction<N>) -> Result<Confirmation<N>> {
+        let transaction_id = transaction.id();
+        ensure!(
+            !transaction.is_fee(),
+            "The transaction is a fee transaction and cannot be broadcast"
+        );
+        let block = self.advance_to_next_block(vec![transaction])?;
+        println!(
+            "⌛ Transaction {transaction_id} has been included in local block {}.",
+            block.height()
+        );
+        Confirmation::from_block(&block, &transaction_id)
+    }
+
+    fn get_latest_height(&self) -> Result<u32> {
+        Ok(self.latest_height())
+    }
+
+    fn get_block(&self, height: u32) -> Result<Block<N>> {
+        block_at(&self.vm(), height)
+    }
+
+    fn contains_serial_number(&self, serial_number: &Field<N>) -> Result<bool> {
+        self.vm()
+            .transition_store()
+            .contains_serial_number(serial_number)
+    }
+
+    fn genesis_account(&self) -> Result<Account<N>> {
+        Account::try_from(self.private_key)
+    }
+}
+
+/// Returns the block at the given height in the VM.
+fn block_at<N: Network>(vm: &VM<N, ConsensusMemory<N>>, height: u32) -> Result<Block<N>> {
+    let block_store = vm.block_store();
+    let block = match block_store.get_block_hash(height)? {
+        Some(hash) => block_store.get_block(&hash)?,
+        None => None,
+    };
+    block.ok_or_else(|| anyhow!("Block {height} does not exist in the local ledger"))
+}
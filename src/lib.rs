@@ -8,16 +8,45 @@
 //! - Aleo RPC support
 //!
 
+extern crate self as leology;
+
 pub mod account;
 pub mod api;
+pub mod backend;
 pub mod bindings;
+pub mod config;
+pub mod confirmation;
+pub mod credits;
+pub mod devnet;
+pub mod error;
+pub mod execution;
+pub mod fee;
+pub mod ledger;
+pub mod network;
+pub mod test_accounts;
+pub mod wallet;
+pub mod workspace;
 pub use account::Account;
 pub use api::new_account;
+pub use backend::Backend;
+pub use config::LeologyConfig;
+pub use credits::{Credits, CreditsRecord};
+pub use devnet::{Devnet, DevnetStatus};
+pub use error::LeologyError;
+pub use execution::ExecutionReport;
+pub use fee::FeeStrategy;
+pub use ledger::{LocalLedger, SnapshotId};
+pub use network::AleoNetwork;
+pub use test_accounts::TestAccounts;
+pub use wallet::Wallet;
+pub use workspace::Workspace;
 pub use snarkvm::circuit::AleoTestnetV0 as Aleo;
 pub use snarkvm::ledger::block::Transaction;
 pub use snarkvm::package::Package;
 pub use snarkvm::prelude::*;
 
+pub use snarkvm::console::network::{CanaryV0, MainnetV0, TestnetV0};
+/// The network used when none is given.
 pub use snarkvm::console::network::TestnetV0 as Nw;
 pub const SNARKVM_CONTRACTS_BUILD_FOLDER: &str = "build";
 pub const DEFAULT_ENDPOINT: &str = "http://localhost:3030";
@@ -1,5 +1,9 @@
-use clap::{Parser, Subcommand};
-use std::process::Command;
+use clap::{Args as ClapArgs, Parser, Subcommand};
+use leology::devnet;
+use leology::devnet::node::{self, NodeOptions, DEFAULT_DATA_DIR};
+use leology::{AleoNetwork, CanaryV0, Devnet, LeologyConfig, MainnetV0, SnapshotId, TestnetV0};
+use std::path::PathBuf;
+use std::time::Duration;
 
 /// Command Line Interface for the Leology Test Framework.
 #[derive(Parser, Debug)]
@@ -13,45 +17,189 @@ struct Args {
 #[derive(Subcommand, Debug)]
 enum Commands {
     /// Subcommand to start the local devnet
-    Start,
+    Start {
+        #[command(flatten)]
+        node: NodeArgs,
+        /// The snarkOS binary to run, instead of building the leology fork of snarkOS
+        #[arg(long)]
+        snarkos: Option<PathBuf>,
+        /// Keep running in the foreground until the devnet exits
+        #[arg(long)]
+        foreground: bool,
+    },
     /// Subcommand to stop the local devnet
-    Stop,
+    Stop {
+        #[command(flatten)]
+        node: NodeArgs,
+    },
+    /// Subcommand to save the ledger of the devnet, restarting it if it is running
+    Snapshot {
+        #[command(flatten)]
+        node: NodeArgs,
+        /// The snarkOS binary to restart the devnet with
+        #[arg(long)]
+        snarkos: Option<PathBuf>,
+    },
+    /// Subcommand to restore the ledger saved by a snapshot, restarting the devnet if it is running
+    Revert {
+        #[command(flatten)]
+        node: NodeArgs,
+        /// The snarkOS binary to restart the devnet with
+        #[arg(long)]
+        snarkos: Option<PathBuf>,
+        /// The ID printed by the snapshot subcommand
+        id: u32,
+    },
+    /// Subcommand to report the health of the devnet, failing if it is unreachable
+    Status {
+        #[command(flatten)]
+        config: ConfigArgs,
+        /// Also list the deployed programs, which fetches every block of the devnet
+        #[arg(long)]
+        programs: bool,
+    },
+    /// Subcommand to wait until the devnet produces blocks
+    Wait {
+        #[command(flatten)]
+        config: ConfigArgs,
+        /// How many seconds to wait before failing, instead of the configured ready timeout
+        #[arg(long)]
+        timeout: Option<u64>,
+    },
+    /// Subcommand to wait until the devnet produced the given number of blocks
+    Mine {
+        #[command(flatten)]
+        config: ConfigArgs,
+        /// How many blocks to wait for
+        #[arg(long, default_value_t = 1)]
+        blocks: u32,
+    },
 }
 
-fn main() {
+/// Overrides of the configuration loaded from `leology.toml`, `.env` and the environment.
+#[derive(ClapArgs, Debug)]
+struct ConfigArgs {
+    /// The base URL of the node's REST API
+    #[arg(long)]
+    endpoint: Option<String>,
+    /// The network segment of the REST API paths
+    #[arg(long)]
+    network: Option<String>,
+}
+
+impl ConfigArgs {
+    /// Loads the configuration and applies the overrides.
+    fn load(self) -> anyhow::Result<LeologyConfig> {
+        let mut config = LeologyConfig::load()?;
+        if let Some(endpoint) = self.endpoint {
+            config.endpoint = endpoint;
+        }
+        if let Some(network) = self.network {
+            config.network = network;
+        }
+        Ok(config)
+    }
+}
+
+/// Options shared by the subcommands managing the devnet process.
+#[derive(ClapArgs, Debug)]
+struct NodeArgs {
+    /// The directory holding the pidfile, the logs and the ledger of the devnet
+    #[arg(long, default_value = DEFAULT_DATA_DIR)]
+    data_dir: PathBuf,
+}
+
+fn main() -> anyhow::Result<()> {
     let args = Args::parse();
 
     match args.command {
-        Commands::Start => {
+        Commands::Start {
+            node,
+            snarkos,
+            foreground,
+        } => {
             println!("Starting the blockchain...");
-            devnet_start().expect("Failure starting the devnet");
+            let options = NodeOptions {
+                data_dir: node.data_dir,
+                snarkos,
+            };
+            let mut process = node::start(&options)?;
+            println!(
+                "Devnet started (pid {}), logging to {}",
+                process.child.id(),
+                process.log_file.display()
+            );
+            if foreground {
+                let status = process.child.wait()?;
+                node::stop(&options)?;
+                println!("Devnet exited with {status}");
+            }
         }
-        Commands::Stop => {
+        Commands::Stop { node } => {
             println!("Stopping the blockchain...");
-            devnet_stop().expect("Failure stopping the devnet");
+            let options = NodeOptions {
+                data_dir: node.data_dir,
+                ..Default::default()
+            };
+            match node::stop(&options)? {
+                Some(pid) => println!("Devnet stopped (pid {pid})"),
+                None => println!("The devnet is not running"),
+            }
+        }
+        Commands::Snapshot { node, snarkos } => {
+            let options = NodeOptions {
+                data_dir: node.data_dir,
+                snarkos,
+            };
+            let snapshot = node::snapshot(&options)?;
+            println!("Saved snapshot {snapshot}");
+        }
+        Commands::Revert { node, snarkos, id } => {
+            let options = NodeOptions {
+                data_dir: node.data_dir,
+                snarkos,
+            };
+            node::revert(&options, SnapshotId(id))?;
+            println!("Reverted to snapshot {id}");
+        }
+        Commands::Status { config, programs } => {
+            let devnet = Devnet::with_config(config.load()?);
+            let status = match devnet.config().network.as_str() {
+                MainnetV0::SHORT_NAME => describe_status::<MainnetV0>(&devnet, programs)?,
+                TestnetV0::SHORT_NAME => describe_status::<TestnetV0>(&devnet, programs)?,
+                CanaryV0::SHORT_NAME => describe_status::<CanaryV0>(&devnet, programs)?,
+                network => anyhow::bail!("Unknown network '{network}'"),
+            };
+            println!("Devnet at {}", devnet.endpoint());
+            println!("{status}");
+        }
+        Commands::Wait { config, timeout } => {
+            let mut config = config.load()?;
+            if let Some(timeout) = timeout {
+                config.ready_timeout = Duration::from_secs(timeout);
+            }
+            println!("Waiting for the devnet at {}...", config.endpoint);
+            let height = devnet::wait_until_ready(&config)?;
+            println!("Devnet ready at block {height}");
+        }
+        Commands::Mine { config, blocks } => {
+            let config = config.load()?;
+            println!(
+                "Waiting for {blocks} blocks from the devnet at {}...",
+                config.endpoint
+            );
+            let height = devnet::wait_for_blocks(&config, blocks)?;
+            println!("Devnet at block {height}");
         }
     }
+    Ok(())
 }
 
-/// Starts the devnet chain
-pub fn devnet_start() -> std::io::Result<()> {
-    execute_script("start.sh")
-}
-
-/// Stops the devnet chain
-pub fn devnet_stop() -> std::io::Result<()> {
-    execute_script("stop.sh")
-}
-fn execute_script(command: &str) -> std::io::Result<()> {
-    // Execute the bash script using the Command module
-    let status = Command::new("bash").arg(command).status()?;
-
-    // Check if the script executed successfully
-    if status.success() {
-        println!("Script executed successfully");
-    } else {
-        println!("Script execution failed");
+/// Queries the health of the devnet running the network `N`, and its deployed programs if asked to.
+fn describe_status<N: AleoNetwork>(devnet: &Devnet, programs: bool) -> anyhow::Result<String> {
+    let mut status = devnet.status::<N>()?;
+    if programs {
+        status.programs = Some(devnet.deployed_programs::<N>()?);
     }
-
-    Ok(())
+    Ok(status.to_string())
 }
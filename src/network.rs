@@ -0,0 +1,27 @@
+use crate::*;
+use snarkvm::circuit::{AleoCanaryV0, AleoTestnetV0, AleoV0};
+
+/// A network leology can target, paired with the circuit that synthesizes its proofs.
+///
+/// Implemented for mainnet, testnet and canary. [`Nw`] is the network used when none is given.
+pub trait AleoNetwork: Network {
+    /// The circuit environment of the network.
+    type Circuit: snarkvm::circuit::Aleo<Network = Self, BaseField = Self::Field>;
+    /// The network segment of the snarkOS REST API paths.
+    const SHORT_NAME: &'static str;
+}
+
+impl AleoNetwork for MainnetV0 {
+    type Circuit = AleoV0;
+    const SHORT_NAME: &'static str = "mainnet";
+}
+
+impl AleoNetwork for TestnetV0 {
+    type Circuit = AleoTestnetV0;
+    const SHORT_NAME: &'static str = "testnet";
+}
+
+impl AleoNetwork for CanaryV0 {
+    type Circuit = AleoCanaryV0;
+    const SHORT_NAME: &'static str = "canary";
+}
@@ -0,0 +1,152 @@
+use crate::bindings::*;
+use crate::*;
+use rand::SeedableRng;
+use rand_chacha::ChaChaRng;
+
+/// The names of the accounts derived by [`TestAccounts`], in order.
+pub const ACCOUNT_NAMES: [&str; 8] = [
+    "alice", "bob", "carol", "dave", "eve", "frank", "grace", "heidi",
+];
+
+/// Named accounts derived deterministically from a seed, so that every test run uses the same
+/// addresses.
+///
+/// The accounts are derived in the order of [`ACCOUNT_NAMES`], so `alice` is the same account
+/// whatever the number of accounts.
+#[derive(Clone, Debug)]
+pub struct TestAccounts<N: Network = Nw> {
+    /// The accounts, by name.
+    accounts: IndexMap<&'static str, Account<N>>,
+}
+
+impl<N: Network> TestAccounts<N> {
+    /// Derives `count` named accounts from the seed.
+    pub fn derive(seed: u64, count: usize) -> Result<Self> {
+        ensure!(
+            count <= ACCOUNT_NAMES.len(),
+            "At most {} test accounts can be derived, {count} were requested",
+            ACCOUNT_NAMES.len()
+        );
+        let rng = &mut ChaChaRng::seed_from_u64(seed);
+        let accounts = ACCOUNT_NAMES[..count]
+            .iter()
+            .map(|name| Ok((*name, Account::new(rng)?)))
+            .collect::<Result<_>>()?;
+        Ok(Self { accounts })
+    }
+
+    /// Derives `count` named accounts from the seed, and funds each of them with `microcredits`
+    /// public credits from the genesis account of the backend.
+    pub fn setup(
+        backend: &dyn Backend<N>,
+        seed: u64,
+        count: usize,
+        microcredits: u64,
+    ) -> Result<Self> {
+        let accounts = Self::derive(seed, count)?;
+        accounts.fund(&backend.genesis_account()?, backend, microcredits)?;
+        Ok(accounts)
+    }
+
+    /// Tops up the public balance of every account to at least `microcredits`, with
+    /// `credits.aleo/transfer_public` executions paid by the funder.
+    ///
+    /// Accounts already holding enough credits, for instance on a devnet reused across test
+    /// runs, are left untouched.
+    pub fn fund(
+        &self,
+        funder: &Account<N>,
+        backend: &dyn Backend<N>,
+        microcredits: u64,
+    ) -> Result<()> {
+        let vm = VM::from(ConsensusStore::<N, ConsensusMemory<N>>::open(0u16)?)?;
+        let rng = &mut rand::thread_rng();
+        for (name, account) in &self.accounts {
+            let balance = backend.get_public_balance(&account.address())?;
+            if balance >= microcredits {
+                continue;
+            }
+            let inputs = [
+                ToValue::<N>::to_value(&account.address()),
+                ToValue::<N>::to_value(&(microcredits - balance)),
+            ];
+            let transaction = vm.execute(
+                funder.private_key(),
+                ("credits.aleo", "transfer_public"),
+                inputs.into_iter(),
+                None,
+                0,
+                Some(backend.query()),
+                rng,
+            )?;
+            backend
+                .broadcast_transaction(transaction)?
+                .ensure_accepted()
+                .with_context(|| format!("Failed to fund {name}"))?;
+            println!(
+                "💸 Funded {name} ({}) with {} microcredits",
+                account.address(),
+                microcredits - balance
+            );
+        }
+        Ok(())
+    }
+
+    /// Returns the account with the given name, failing if it was not derived.
+    pub fn get(&self, name: &str) -> Result<&Account<N>> {
+        self.accounts.get(name).ok_or_else(|| {
+            anyhow!(
+                "There is no test account named '{name}' among the {} derived",
+                self.accounts.len()
+            )
+        })
+    }
+
+    /// Returns the accounts, in derivation order, with their names.
+    pub fn iter(&self) -> impl Iterator<Item = (&'static str, &Account<N>)> {
+        self.accounts.iter().map(|(name, account)| (*name, account))
+    }
+
+    /// Returns the first account, failing if no account was derived.
+    pub fn alice(&self) -> Result<&Account<N>> {
+        self.get("alice")
+    }
+
+    /// Returns the second account, failing if fewer accounts were derived.
+    pub fn bob(&self) -> Result<&Account<N>> {
+        self.get("bob")
+    }
+
+    /// Returns the third account, failing if fewer accounts were derived.
+    pub fn carol(&self) -> Result<&Account<N>> {
+        self.get("carol")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_derive() {
+        let accounts = TestAccounts::<Nw>::derive(7, 3).unwrap();
+        let names: Vec<&str> = accounts.iter().map(|(name, _)| name).collect();
+        assert_eq!(names, ["alice", "bob", "carol"]);
+
+        // The accounts only depend on the seed and their position.
+        let more = TestAccounts::<Nw>::derive(7, 5).unwrap();
+        let alice = accounts.alice().unwrap().address();
+        assert_eq!(more.alice().unwrap().address(), alice);
+        assert_eq!(
+            more.get("carol").unwrap().address(),
+            accounts.carol().unwrap().address()
+        );
+        assert_ne!(alice, accounts.bob().unwrap().address());
+        let other = TestAccounts::<Nw>::derive(8, 1).unwrap();
+        assert_ne!(other.alice().unwrap().address(), alice);
+
+        assert!(accounts.get("dave").is_err());
+        assert!(other.bob().is_err());
+        assert!(TestAccounts::<Nw>::derive(7, ACCOUNT_NAMES.len() + 1).is_err());
+    }
+}
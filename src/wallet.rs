@@ -0,0 +1,183 @@
+use crate::bindings::*;
+use crate::*;
+use std::collections::HashSet;
+use std::sync::{Arc, Mutex};
+
+/// The records owned by an account, found by scanning the blocks of a backend with its view key.
+///
+/// Every query first scans the blocks produced since the previous one, so the wallet follows the
+/// ledger as transactions are broadcast. If the ledger was reverted, the wallet scans it again
+/// from the genesis block.
+pub struct Wallet<N: Network = Nw> {
+    /// The account owning the records.
+    account: Account<N>,
+    /// The chain the records are found on.
+    backend: Arc<dyn Backend<N>>,
+    /// The records found so far.
+    state: Mutex<WalletState<N>>,
+}
+
+/// The blocks scanned by a [`Wallet`], and what it found in them.
+struct WalletState<N: Network> {
+    /// The height and hash of the latest scanned block.
+    scanned: Option<(u32, N::BlockHash)>,
+    /// The records owned by the account, in the order they were created.
+    records: Vec<OwnedRecord<N>>,
+    /// The serial numbers of every record spent in the scanned blocks.
+    serial_numbers: HashSet<Field<N>>,
+}
+
+/// A record owned by the account of a [`Wallet`].
+#[derive(Clone, Debug)]
+pub struct OwnedRecord<N: Network> {
+    /// The program whose transition created the record.
+    pub program_id: ProgramID<N>,
+    /// The commitment of the record.
+    pub commitment: Field<N>,
+    /// The serial number revealed when the record is spent.
+    pub serial_number: Field<N>,
+    /// The height of the block that created the record.
+    pub height: u32,
+    /// The decrypted record.
+    pub record: Record<N, Plaintext<N>>,
+}
+
+impl<N: Network> Account<N> {
+    /// Returns a wallet tracking the records of the account on the backend.
+    pub fn wallet(&self, backend: impl Backend<N> + 'static) -> Wallet<N> {
+        Wallet::new(self.clone(), Arc::new(backend))
+    }
+}
+
+impl<N: Network> Wallet<N> {
+    /// Returns a wallet tracking the records of the account on a shared backend.
+    pub fn new(account: Account<N>, backend: Arc<dyn Backend<N>>) -> Self {
+        Self {
+            account,
+            backend,
+            state: Mutex::new(WalletState {
+                scanned: None,
+                records: Vec::new(),
+                serial_numbers: HashSet::new(),
+            }),
+        }
+    }
+
+    /// Returns the account owning the records.
+    pub const fn account(&self) -> &Account<N> {
+        &self.account
+    }
+
+    /// Returns the chain the records are found on.
+    pub fn backend(&self) -> &dyn Backend<N> {
+        self.backend.as_ref()
+    }
+
+    /// Scans the blocks produced since the previous scan, and returns the latest height.
+    pub fn sync(&self) -> Result<u32> {
+        let mut state = self.state.lock().unwrap();
+        let latest_height = self.backend.get_latest_height()?;
+        let next_height = match state.scanned {
+            Some((height, hash))
+                if height <= latest_height && self.backend.get_block(height)?.hash() == hash =>
+            {
+                height + 1
+            }
+            Some(_) => {
+                // The scanned blocks were reverted.
+                state.records.clear();
+                state.serial_numbers.clear();
+                0
+            }
+            None => 0,
+        };
+        for height in next_height..=latest_height {
+            let block = self.backend.get_block(height)?;
+            for transition in block.transitions() {
+                state
+                    .serial_numbers
+                    .extend(transition.serial_numbers().copied());
+                for (commitment, record) in transition.records() {
+                    if !record.is_owner(self.account.view_key()) {
+                        continue;
+                    }
+                    let serial_number = Record::<N, Plaintext<N>>::serial_number(
+                        *self.account.private_key(),
+                        *commitment,
+                    )?;
+                    state.records.push(OwnedRecord {
+                        program_id: *transition.program_id(),
+                        commitment: *commitment,
+                        serial_number,
+                        height,
+                        record: record.decrypt(self.account.view_key())?,
+                    });
+                }
+            }
+            state.scanned = Some((height, block.hash()));
+        }
+        Ok(latest_height)
+    }
+
+    /// Returns every record of the account, spent or not.
+    pub fn all(&self) -> Result<Vec<OwnedRecord<N>>> {
+        self.sync()?;
+        Ok(self.state.lock().unwrap().records.clone())
+    }
+
+    /// Returns the records of the account that were not spent yet.
+    pub fn unspent(&self) -> Result<Vec<OwnedRecord<N>>> {
+        self.sync()?;
+        let state = self.state.lock().unwrap();
+        Ok(state
+            .records
+            .iter()
+            .filter(|owned| !state.serial_numbers.contains(&owned.serial_number))
+            .cloned()
+            .collect())
+    }
+
+    /// Returns `true` if a record with the given serial number was spent in the scanned blocks.
+    pub fn is_spent(&self, serial_number: &Field<N>) -> Result<bool> {
+        self.sync()?;
+        Ok(self
+            .state
+            .lock()
+            .unwrap()
+            .serial_numbers
+            .contains(serial_number))
+    }
+
+    /// Returns the unspent records of the account of the given type, such as `Rec`.
+    pub fn records<R: ProgramRecord<N>>(&self) -> Result<Vec<R>> {
+        let program_id = R::PROGRAM_ID.map(ProgramID::<N>::from_str).transpose()?;
+        let record_name = Identifier::<N>::from_str(R::NAME)?;
+        let mut records = Vec::new();
+        for owned in self.unspent()? {
+            if program_id.is_some_and(|program_id| program_id != owned.program_id) {
+                continue;
+            }
+            // Records of another type do not open to the same commitment.
+            if owned
+                .record
+                .to_commitment(&owned.program_id, &record_name)?
+                == owned.commitment
+            {
+                records.push(R::from_record(owned.record));
+            }
+        }
+        Ok(records)
+    }
+
+    /// Returns the microcredits held by the unspent `credits.aleo` records of the account.
+    pub fn unspent_credits(&self) -> Result<u64> {
+        let credits = ProgramID::<N>::from_str("credits.aleo")?;
+        let mut microcredits = 0u64;
+        for owned in self.unspent()? {
+            if owned.program_id == credits {
+                microcredits = microcredits.saturating_add(record_microcredits(&owned.record)?);
+            }
+        }
+        Ok(microcredits)
+    }
+}
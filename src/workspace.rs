@@ -0,0 +1,202 @@
+use crate::bindings::*;
+use crate::*;
+use std::fs;
+
+/// Several compiled Leo packages, deployed together so that every program is deployed after the
+/// programs it imports.
+pub struct Workspace<N: Network> {
+    /// The compiled package of each program.
+    packages: IndexMap<ProgramID<N>, Package<N>>,
+}
+
+impl<N: Network> Default for Workspace<N> {
+    fn default() -> Self {
+        Self {
+            packages: IndexMap::new(),
+        }
+    }
+}
+
+impl<N: AleoNetwork> Workspace<N> {
+    /// Initializes an empty workspace.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Finds every Leo package under `root`, recursively.
+    ///
+    /// A directory is a Leo package if it contains a compiled program in its `build` folder.
+    pub fn discover(root: impl AsRef<Path>) -> Result<Self> {
+        let mut workspace = Self::new();
+        let mut directories = vec![root.as_ref().to_path_buf()];
+        while let Some(directory) = directories.pop() {
+            let build_directory = directory.join(SNARKVM_CONTRACTS_BUILD_FOLDER);
+            if build_directory.join("main.aleo").is_file() {
+                workspace = workspace.with_package(&directory)?;
+                continue;
+            }
+            let mut subdirectories = Vec::new();
+            for entry in fs::read_dir(&directory)? {
+                let path = entry?.path();
+                let is_hidden = path
+                    .file_name()
+                    .is_some_and(|name| name.to_string_lossy().starts_with('.'));
+                if path.is_dir() && !is_hidden && !path.ends_with("target") {
+                    subdirectories.push(path);
+                }
+            }
+            // Visit the subdirectories in alphabetical order.
+            subdirectories.sort_unstable_by(|a, b| b.cmp(a));
+            directories.extend(subdirectories);
+        }
+        Ok(workspace)
+    }
+
+    /// Adds the Leo package at `package_directory`, whose compiled program is in its `build` folder.
+    pub fn with_package(mut self, package_directory: impl AsRef<Path>) -> Result<Self> {
+        let build_directory = package_directory
+            .as_ref()
+            .join(SNARKVM_CONTRACTS_BUILD_FOLDER);
+        let package = Package::open(&build_directory)?;
+        let program_id = *package.program_id();
+        ensure!(
+            !self.packages.contains_key(&program_id),
+            "'{program_id}' is defined by several packages, including '{}'",
+            package_directory.as_ref().display()
+        );
+        self.packages.insert(program_id, package);
+        Ok(self)
+    }
+
+    /// Returns the IDs of the programs in the workspace.
+    pub fn program_ids(&self) -> impl Iterator<Item = &ProgramID<N>> {
+        self.packages.keys()
+    }
+
+    /// Returns the IDs of the programs in the workspace, each after the programs it imports.
+    ///
+    /// Imports from outside the workspace are expected to be deployed already.
+    pub fn deployment_order(&self) -> Result<Vec<ProgramID<N>>> {
+        let mut order = Vec::with_capacity(self.packages.len());
+        for program_id in self.packages.keys() {
+            self.visit(program_id, &mut Vec::new(), &mut order)?;
+        }
+        Ok(order)
+    }
+
+    /// Appends the imports of the program and then the program itself to `order`, unless they
+    /// were already appended.
+    fn visit(
+        &self,
+        program_id: &ProgramID<N>,
+        path: &mut Vec<ProgramID<N>>,
+        order: &mut Vec<ProgramID<N>>,
+    ) -> Result<()> {
+        let Some(package) = self.packages.get(program_id) else {
+            return Ok(());
+        };
+        if order.contains(program_id) {
+            return Ok(());
+        }
+        if path.contains(program_id) {
+            let cycle: Vec<String> = path.iter().map(ToString::to_string).collect();
+            bail!(
+                "The imports of '{program_id}' form a cycle: {}",
+                cycle.join(" -> ")
+            );
+        }
+        path.push(*program_id);
+        for import_program_id in package.program().imports().keys() {
+            self.visit(import_program_id, path, order)?;
+        }
+        path.pop();
+        order.push(*program_id);
+        Ok(())
+    }
+
+    /// Deploys the programs of the workspace to the backend in dependency order, skipping the
+    /// programs that are already deployed with the same bytecode.
+    ///
+    /// Returns the IDs of the programs that were deployed.
+    pub fn deploy(
+        &self,
+        deployer: &Account<N>,
+        backend: impl Backend<N> + 'static,
+        fee_strategy: &FeeStrategy<N>,
+    ) -> Result<Vec<ProgramID<N>>> {
+        let wallet = deployer.wallet(backend);
+        let backend = wallet.backend();
+        let mut deployed = Vec::new();
+        for program_id in self.deployment_order()? {
+            let package = &self.packages[&program_id];
+            match backend.find_program(&program_id)? {
+                Some(program) if &program == package.program() => {
+                    println!("♻️ Reusing the deployment of '{program_id}'");
+                }
+                Some(_) => bail!(
+                    "'{program_id}' is already deployed with a different bytecode than '{}'",
+                    package.directory().join("main.aleo").display()
+                ),
+                None => {
+                    deploy_package(&wallet, package, fee_strategy)?;
+                    deployed.push(program_id);
+                }
+            }
+        }
+        Ok(deployed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Writes a compiled Leo package for the program under `root`.
+    fn write_package(root: &Path, name: &str, program: &str) {
+        let build_directory = root.join(name).join(SNARKVM_CONTRACTS_BUILD_FOLDER);
+        fs::create_dir_all(&build_directory).unwrap();
+        fs::write(
+            build_directory.join("program.json"),
+            format!(r#"{{ "program": "{name}.aleo", "version": "0.0.0", "description": "", "license": "MIT" }}"#),
+        )
+        .unwrap();
+        fs::write(build_directory.join("main.aleo"), program).unwrap();
+    }
+
+    #[test]
+    fn test_deployment_order() {
+        let root = std::env::temp_dir().join(format!("leology-workspace-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        write_package(
+            &root,
+            "app",
+            "import base.aleo;\nimport lib.aleo;\nprogram app.aleo;\n\nfunction main:\n    input r0 as u8.public;\n    output r0 as u8.public;\n",
+        );
+        write_package(
+            &root,
+            "lib",
+            "import base.aleo;\nprogram lib.aleo;\n\nfunction main:\n    input r0 as u8.public;\n    output r0 as u8.public;\n",
+        );
+        write_package(
+            &root,
+            "base",
+            "program base.aleo;\n\nfunction main:\n    input r0 as u8.public;\n    output r0 as u8.public;\n",
+        );
+
+        let workspace = Workspace::<Nw>::discover(&root).unwrap();
+        let order: Vec<String> = workspace
+            .deployment_order()
+            .unwrap()
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+        assert_eq!(order, ["base.aleo", "lib.aleo", "app.aleo"]);
+
+        assert!(Workspace::<Nw>::new()
+            .with_package(root.join("base"))
+            .unwrap()
+            .with_package(root.join("base"))
+            .is_err());
+        fs::remove_dir_all(&root).unwrap();
+    }
+}